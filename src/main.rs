@@ -1,8 +1,18 @@
+use chrono::{Duration, Local, Utc};
+use ethers::{types::U256, utils::format_ether};
 use kite_ai_bot::{
-    model::{exception::operation_error::OperationError, spinner_data::SpinnerData},
+    model::{
+        account_context::AccountContext, agent::Agent, exception::operation_error::OperationError,
+        spinner_data::SpinnerData,
+    },
     repository::api_repository::ApiRepository,
-    service::{db::rustqlite::RustQLite, evm_service::evm_service::EvmService},
+    service::{
+        db::rustqlite::RustQLite,
+        evm_service::{evm_service::EvmService, rpc_pool::RpcPool},
+    },
     utils::{
+        account_vault::AccountVault,
+        cli::Command,
         configuration::Config,
         exception_handler::ExceptionHandler,
         helper::Helper,
@@ -12,7 +22,7 @@ use kite_ai_bot::{
     },
 };
 use rust_decimal::{prelude::FromPrimitive, Decimal};
-use std::sync::Arc;
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 #[tokio::main]
 async fn main() {
@@ -21,39 +31,49 @@ async fn main() {
 
     Config::init().expect("Failed to initialize Configuration");
     logger::init_logger().expect("Failed to initialize logger");
-    RustQLite::init().await;
-    Spinner::init().await;
+    let db = RustQLite::init()
+        .await
+        .expect("Failed to initialize database");
 
-    let account_list = Helper::read_data_from_file("accounts.json");
-    let proxy_list = Helper::read_data_from_file("proxy_list.json");
-    if account_list.is_none() {
-        panic!("No Valid Accounts Found");
+    if let Some(command) = &Config::get().command {
+        run_command(command, &db).await;
+        return;
     }
 
-    if let Some(accounts) = &account_list {
-        if let Some(proxies) = &proxy_list {
-            if proxies.len() > 0 && accounts.len() != proxies.len() {
-                panic!(
-                    "You have {} accounts but only {} proxies.",
-                    accounts.len(),
-                    proxies.len()
-                );
-            }
+    Spinner::init().await;
+
+    AccountVault::init().expect("Failed to load accounts");
+    let accounts = AccountVault::contexts();
+    let mut verified: Vec<&str> = vec![];
+    for acc in accounts.iter().filter(|acc| acc.enabled) {
+        let network = Config::get().network_for(acc);
+        if verified.contains(&network.name.as_str()) {
+            continue;
+        }
+        let proxy = if Config::get().rpc_use_proxy {
+            acc.proxy.to_owned()
         } else {
-            panic!("Proxy file not detected, please provice proxy_list.json");
+            None
+        };
+        let verified_chain = match RpcPool::new(network, proxy, Config::get().rpc_health.clone()) {
+            Ok(pool) => pool.verify_chain_id().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = verified_chain {
+            panic!("Refusing to sign transactions: {}", err);
         }
-    } else {
-        panic!("Accounts file not detected, please provice accounts.json");
+        verified.push(&network.name);
     }
 
-    let accounts = account_list.unwrap();
     let mut tasks = vec![];
 
-    for key in accounts.iter() {
-        let account = key.clone();
+    for acc in accounts.iter().filter(|acc| acc.enabled) {
+        let acc = Arc::clone(acc);
+        let db = Arc::clone(&db);
+        let network = Config::get().network_for(&acc);
 
         let task = tokio::spawn(async move {
-            operation(&account).await;
+            operation(acc, network, db).await;
         });
 
         tasks.push(task);
@@ -64,20 +84,130 @@ async fn main() {
     }
 }
 
-async fn operation(acc: &str) {
-    let acc = Arc::new(acc.to_string());
+async fn run_command(command: &Command, db: &RustQLite) {
+    match command {
+        Command::ImportKeys { source } => {
+            let vault = AccountVault::vault_path();
+            let imported = AccountVault::passphrase(!Path::new(&vault).exists())
+                .and_then(|passphrase| AccountVault::import(source, &vault, &passphrase))
+                .expect("Failed to import accounts");
+            println!(
+                "Imported {} accounts from {} into {}\nVerify the bot can unlock {} then remove {} from this server",
+                imported, source, vault, vault, source
+            );
+        }
+        Command::Transactions { address, limit } => {
+            let transactions = db
+                .get_transactions(address, *limit)
+                .await
+                .expect("Failed to read transactions");
+            let total_gas = db
+                .get_total_gas_spent(address)
+                .await
+                .expect("Failed to read gas spent");
+            let config = Config::get();
+
+            println!("Transactions of {}", address);
+            for tx in transactions.iter() {
+                println!(
+                    "{} | nonce {} | {} | block {} | gas {} | {}",
+                    tx.created_at,
+                    tx.nonce,
+                    tx.status,
+                    tx.block_number
+                        .map_or("-".to_string(), |block| block.to_string()),
+                    tx.gas_used.map_or("-".to_string(), |gas| gas.to_string()),
+                    tx.hash
+                );
+            }
+            println!("Transactions Shown : {}", transactions.len());
+            if total_gas.is_empty() {
+                println!("Total Gas Spent    : 0");
+            }
+            for (chain_id, total) in total_gas {
+                let network = match chain_id {
+                    Some(chain_id) => Network::find_by_chain_id(&config.networks, chain_id),
+                    None => Network::find(&config.networks, &config.default_network),
+                };
+                println!(
+                    "Total Gas Spent    : {} {}",
+                    format_ether(U256::from(total)),
+                    network.map_or(
+                        format!("(chain {})", chain_id.unwrap_or_default()),
+                        |network| network.symbol.to_owned()
+                    )
+                );
+            }
+        }
+    }
+}
+
+async fn refresh_daily_stats(
+    acc: &AccountContext,
+    address: &str,
+    db: &RustQLite,
+    interaction_limit: u32,
+) {
+    let budget = match db.get_interaction_budget(address, interaction_limit).await {
+        Ok(budget) => Some(budget),
+        Err(error) => {
+            ExceptionHandler::db_error(acc, error).await;
+            None
+        }
+    };
+
+    let mut actions_today = HashMap::new();
+    if acc.use_onchain(Config::get().use_onchain) {
+        let actions = Config::get()
+            .onchain_actions
+            .for_account(acc.number(), &acc.address);
+        for action in actions.iter().filter(|action| action.enabled) {
+            let log_type = action.log_type();
+            match db.get_logs_today(address, &log_type).await {
+                Ok(logs) => {
+                    actions_today.insert(log_type, logs.len());
+                }
+                Err(error) => {
+                    ExceptionHandler::db_error(acc, error).await;
+                    break;
+                }
+            }
+        }
+    }
+
+    SpinnerData::update(acc.index, |data| {
+        data.budget = budget;
+        data.actions_today = actions_today;
+    });
+}
+
+async fn operation(acc: Arc<AccountContext>, network: &Network, db: Arc<RustQLite>) {
+    let interaction_limit = acc.interaction_limit(Config::get().interaction);
+    let agents: Vec<&Agent> = Config::get()
+        .agents
+        .iter()
+        .filter(|agent| agent.enabled && acc.allows_agent(agent))
+        .collect();
+    if agents.is_empty() {
+        ExceptionHandler::operation_error(
+            &acc,
+            OperationError::new("No enabled agents assigned to this account, skipping..."),
+        )
+        .await;
+        return;
+    }
 
     loop {
         Spinner::log(&acc, "Initializing Wallet...", 1000).await;
-        let network = Network::KITEAI;
-        let mut evm_service = match EvmService::new(&acc, &network) {
+        let mut evm_service = match EvmService::new(&acc, network, &db) {
             Ok(service) => service,
             Err(err) => {
                 ExceptionHandler::operation_error(&acc, OperationError::from(err)).await;
                 continue;
             }
         };
-        let api_repository = match ApiRepository::new(&acc) {
+        evm_service.check_rpc_health().await;
+        let api_repository = match ApiRepository::new(&acc, &db) {
             Ok(repository) => repository,
             Err(err) => {
                 ExceptionHandler::setup_error(&acc, err).await;
@@ -86,58 +216,146 @@ async fn operation(acc: &str) {
         };
 
         if let Err(error) = evm_service.get_balance().await {
-            ExceptionHandler::operation_error(&acc, OperationError::from(error)).await;
+            ExceptionHandler::operation_error(&acc, error).await;
             continue;
         }
+        if let Err(error) = evm_service.get_token_balances().await {
+            ExceptionHandler::operation_error(&acc, error).await;
+        }
         api_repository
             .get_user_stats(&evm_service.formatted_address)
             .await;
+        refresh_daily_stats(&acc, &evm_service.formatted_address, &db, interaction_limit).await;
 
-        if Config::get().use_onchain {
-            if SpinnerData::get_or_create(&acc).balance.gas > Decimal::from_i32(0).unwrap() {
-                match evm_service.transfer().await {
-                    Ok(()) => {}
-                    Err(error) => {
-                        ExceptionHandler::operation_error(&acc, OperationError::from(error)).await
+        if acc.use_onchain(Config::get().use_onchain) {
+            if SpinnerData::get_or_create(acc.index).balance.gas > Decimal::from_i32(0).unwrap() {
+                let actions = Config::get()
+                    .onchain_actions
+                    .for_account(acc.number(), &acc.address);
+                for action in actions.iter().filter(|action| action.enabled) {
+                    let log_type = action.log_type();
+                    let done = match db
+                        .get_logs_today(&evm_service.formatted_address, &log_type)
+                        .await
+                    {
+                        Ok(logs) => logs.len(),
+                        Err(error) => {
+                            ExceptionHandler::db_error(&acc, error).await;
+                            break;
+                        }
+                    };
+                    if action
+                        .daily_count
+                        .is_some_and(|limit| done >= limit as usize)
+                    {
+                        Spinner::log(
+                            &acc,
+                            &format!("{} Daily Limit Reached, Skipping...", action.name),
+                            1000,
+                        )
+                        .await;
+                        continue;
                     }
+
+                    match evm_service.execute_action(action).await {
+                        Ok(failures) => {
+                            if let Err(error) = db
+                                .insert_log(&evm_service.formatted_address, &log_type)
+                                .await
+                            {
+                                ExceptionHandler::db_error(&acc, error).await;
+                            }
+                            for error in failures {
+                                ExceptionHandler::operation_error(&acc, error).await;
+                            }
+                        }
+                        Err(error) => ExceptionHandler::operation_error(&acc, error).await,
+                    }
+                    refresh_daily_stats(
+                        &acc,
+                        &evm_service.formatted_address,
+                        &db,
+                        interaction_limit,
+                    )
+                    .await;
                 }
             } else {
                 ExceptionHandler::operation_error(
                     &acc,
                     OperationError::new(
-                        &format!(
-                            "You dont have {} to use On Chain Feature",
-                            Network::get_rpc_details(&network).symbol
-                        )
-                        .as_str(),
+                        format!("You dont have {} to use On Chain Feature", network.symbol)
+                            .as_str(),
                     ),
                 )
                 .await
             }
         }
 
-        let delay = 60000;
-        if RustQLite::get_logs_today(&evm_service.formatted_address, "interact")
+        let budget = match db
+            .get_interaction_budget(&evm_service.formatted_address, interaction_limit)
             .await
-            .len()
-            <= Config::get().interaction as usize
         {
-            api_repository
-                .chat_with_professor_agent(&evm_service.formatted_address)
-                .await;
-            Spinner::log(&acc, "Delaying 1 Min Before Chat Other Agent...", 60000).await;
-            api_repository
-                .chat_with_sherlock_agent(&evm_service.formatted_address)
-                .await;
-            Spinner::log(&acc, "Delaying 1 Min Before Chat Other Agent...", 60000).await;
-            api_repository
-                .chat_with_buddy_agent(&evm_service.formatted_address)
-                .await;
+            Ok(budget) => budget,
+            Err(error) => {
+                ExceptionHandler::db_error(&acc, error).await;
+                Spinner::log(&acc, "Database Unavailable, Backing Off...", 60000).await;
+                continue;
+            }
+        };
+
+        let delay = 60000;
+        if !budget.is_exhausted(&agents) {
+            let mut chatted = false;
+            for agent in agents.iter() {
+                let budget = match db
+                    .get_interaction_budget(&evm_service.formatted_address, interaction_limit)
+                    .await
+                {
+                    Ok(budget) => budget,
+                    Err(error) => {
+                        ExceptionHandler::db_error(&acc, error).await;
+                        break;
+                    }
+                };
+                if budget.global_remaining() == 0 {
+                    break;
+                }
+                if budget.remaining_for(agent) == 0 {
+                    Spinner::log(
+                        &acc,
+                        &format!("{} Daily Limit Reached, Skipping...", agent.name),
+                        1000,
+                    )
+                    .await;
+                    continue;
+                }
+
+                if chatted {
+                    Spinner::log(&acc, "Delaying 1 Min Before Chat Other Agent...", 60000).await;
+                }
+                api_repository
+                    .chat_with_agent(agent, &evm_service.formatted_address)
+                    .await;
+                refresh_daily_stats(&acc, &evm_service.formatted_address, &db, interaction_limit)
+                    .await;
+                chatted = true;
+            }
 
             Spinner::log(&acc, "Account Processing Complete...", delay).await;
         } else {
-            let delay = 60000 * 60 * 24;
-            Spinner::log(&acc, "Account Processing Complete...", delay).await;
+            let now = Utc::now();
+            let wake_at = Config::get().quota_window.next_reset(now)
+                + Duration::seconds(Config::get().quota_reset_offset_secs);
+            let delay = (wake_at - now).num_milliseconds().max(0) as u64;
+            Spinner::log(
+                &acc,
+                &format!(
+                    "Daily Interaction Limit Reached, Sleeping Until {}",
+                    wake_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+                ),
+                delay,
+            )
+            .await;
         }
     }
 }
@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    model::agent::Agent,
+    utils::{helper::Helper, network::Network},
+};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub label: Option<String>,
+    pub key: String,
+    pub proxy: Option<String>,
+    pub enabled: bool,
+    pub agents: Option<Vec<String>>,
+    pub daily_limit: Option<u32>,
+    pub use_onchain: Option<bool>,
+    pub network: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum AccountEntry {
+    Key(String),
+    Structured(AccountDefinition),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AccountDefinition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proxy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    agents: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    daily_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    use_onchain: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network: Option<String>,
+}
+
+impl fmt::Debug for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Account")
+            .field("label", &self.label)
+            .field("key", &"<redacted>")
+            .field("proxy", &self.proxy.as_ref().map(|_| "<redacted>"))
+            .field("enabled", &self.enabled)
+            .field("agents", &self.agents)
+            .field("daily_limit", &self.daily_limit)
+            .field("use_onchain", &self.use_onchain)
+            .field("network", &self.network)
+            .finish()
+    }
+}
+
+impl fmt::Debug for AccountEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountEntry::Key(_) => write!(f, "Key(<redacted>)"),
+            AccountEntry::Structured(definition) => f
+                .debug_struct("Structured")
+                .field("label", &definition.label)
+                .field("key", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+impl AccountEntry {
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            AccountEntry::Key(key) => Some(key.trim()),
+            AccountEntry::Structured(definition) => definition.key.as_deref().map(str::trim),
+        }
+    }
+}
+
+impl Account {
+    pub fn new(key: &str) -> Self {
+        Account {
+            label: None,
+            key: key.to_string(),
+            proxy: None,
+            enabled: true,
+            agents: None,
+            daily_limit: None,
+            use_onchain: None,
+            network: None,
+        }
+    }
+
+    pub fn read_entries(path: &str) -> Result<Option<Vec<AccountEntry>>, String> {
+        Helper::read_optional(path)?
+            .map(|data| Self::parse_entries(&data).map_err(|err| format!("{}: {}", path, err)))
+            .transpose()
+    }
+
+    pub fn parse_entries(data: &str) -> Result<Vec<AccountEntry>, String> {
+        serde_json::from_str(data).map_err(|err| format!("Invalid accounts file: {}", err))
+    }
+
+    pub fn from_entries<F>(
+        entries: Vec<AccountEntry>,
+        proxies: Option<Vec<String>>,
+        mut expand: F,
+    ) -> Result<Vec<Account>, String>
+    where
+        F: FnMut(&str) -> Result<Vec<String>, String>,
+    {
+        let legacy = entries
+            .iter()
+            .all(|entry| matches!(entry, AccountEntry::Key(_)));
+
+        let mut accounts = vec![];
+        for (idx, entry) in entries.into_iter().enumerate() {
+            let position = idx + 1;
+            let definition = match entry {
+                AccountEntry::Key(key) => {
+                    accounts.extend(expand(&key)?.iter().map(|key| Account::new(key)));
+                    continue;
+                }
+                AccountEntry::Structured(definition) => definition,
+            };
+
+            let key = definition
+                .key
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .ok_or(format!("Account #{} is missing key", position))?;
+            let label = definition
+                .label
+                .map(|label| label.trim().to_string())
+                .filter(|label| !label.is_empty());
+            let proxy = definition
+                .proxy
+                .map(|proxy| proxy.trim().to_string())
+                .filter(|proxy| !proxy.is_empty());
+            if let Some(proxy) = proxy.as_ref() {
+                if !Helper::is_valid_proxy_format(proxy) {
+                    return Err(format!(
+                        "Account #{}{} has invalid proxy format",
+                        position,
+                        label
+                            .as_ref()
+                            .map_or(String::new(), |label| format!(" ({})", label))
+                    ));
+                }
+            }
+            let agents = definition.agents.map(|agents| {
+                agents
+                    .into_iter()
+                    .map(|agent| agent.trim().to_string())
+                    .filter(|agent| !agent.is_empty())
+                    .collect::<Vec<String>>()
+            });
+            let network = definition
+                .network
+                .map(|network| network.trim().to_string())
+                .filter(|network| !network.is_empty());
+
+            let keys = expand(&key)?;
+            let expanded = keys.len() > 1;
+            for (key_idx, key) in keys.into_iter().enumerate() {
+                accounts.push(Account {
+                    label: label.as_ref().map(|label| {
+                        if expanded {
+                            format!("{}-{}", label, key_idx + 1)
+                        } else {
+                            label.to_owned()
+                        }
+                    }),
+                    key,
+                    proxy: proxy.to_owned(),
+                    enabled: definition.enabled.unwrap_or(true),
+                    agents: agents.to_owned(),
+                    daily_limit: definition.daily_limit,
+                    use_onchain: definition.use_onchain,
+                    network: network.to_owned(),
+                });
+            }
+        }
+
+        if accounts.is_empty() {
+            return Err("No Valid Accounts Found".to_string());
+        }
+
+        if legacy {
+            let proxies = proxies
+                .ok_or("Proxy file not detected, please provice proxy_list.json".to_string())?;
+            if !proxies.is_empty() && accounts.len() != proxies.len() {
+                return Err(format!(
+                    "You have {} accounts but only {} proxies.",
+                    accounts.len(),
+                    proxies.len()
+                ));
+            }
+            for (account, proxy) in accounts.iter_mut().zip(proxies) {
+                account.proxy = Some(proxy);
+            }
+        }
+
+        let mut seen_labels = HashSet::new();
+        let mut seen_keys = HashSet::new();
+        for account in accounts.iter() {
+            if let Some(label) = account.label.as_ref() {
+                if !seen_labels.insert(label.to_lowercase()) {
+                    return Err(format!("Duplicate account label '{}'", label));
+                }
+            }
+            if !seen_keys.insert(account.key.to_owned()) {
+                return Err(format!(
+                    "Duplicate account key{}",
+                    account
+                        .label
+                        .as_ref()
+                        .map_or(String::new(), |label| format!(" for '{}'", label))
+                ));
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    pub fn validate_agents(accounts: &[Account], agents: &[Agent]) -> Result<(), String> {
+        for (idx, account) in accounts.iter().enumerate() {
+            for key in account.agents.iter().flatten() {
+                if !agents.iter().any(|agent| agent.matches(key)) {
+                    return Err(format!(
+                        "Unknown agent '{}' for account {}",
+                        key,
+                        account.label.as_ref().unwrap_or(&(idx + 1).to_string())
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn validate_networks(accounts: &[Account], networks: &[Network]) -> Result<(), String> {
+        for (idx, account) in accounts.iter().enumerate() {
+            if let Some(name) = account.network.as_ref() {
+                if Network::find(networks, name).is_none() {
+                    return Err(format!(
+                        "Unknown network '{}' for account {}",
+                        name,
+                        account.label.as_ref().unwrap_or(&(idx + 1).to_string())
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use super::{account::Account, agent::Agent};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccountContext {
+    pub index: usize,
+    pub label: Option<String>,
+    pub address: String,
+    pub proxy: Option<String>,
+    pub enabled: bool,
+    pub agents: Option<Vec<String>>,
+    pub daily_limit: Option<u32>,
+    pub use_onchain: Option<bool>,
+    pub network: Option<String>,
+}
+
+impl fmt::Debug for AccountContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccountContext")
+            .field("index", &self.index)
+            .field("label", &self.label)
+            .field("address", &self.address)
+            .field("proxy", &self.proxy.as_ref().map(|_| "<redacted>"))
+            .field("enabled", &self.enabled)
+            .field("agents", &self.agents)
+            .field("daily_limit", &self.daily_limit)
+            .field("use_onchain", &self.use_onchain)
+            .field("network", &self.network)
+            .finish()
+    }
+}
+
+impl fmt::Display for AccountContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "{} - {}", self.number(), label),
+            None => write!(f, "{}", self.number()),
+        }
+    }
+}
+
+impl AccountContext {
+    pub fn new(index: usize, account: &Account, address: &str) -> Self {
+        AccountContext {
+            index,
+            label: account.label.to_owned(),
+            address: address.to_lowercase(),
+            proxy: account.proxy.to_owned(),
+            enabled: account.enabled,
+            agents: account.agents.to_owned(),
+            daily_limit: account.daily_limit,
+            use_onchain: account.use_onchain,
+            network: account.network.to_owned(),
+        }
+    }
+
+    pub fn number(&self) -> usize {
+        self.index + 1
+    }
+
+    pub fn allows_agent(&self, agent: &Agent) -> bool {
+        self.agents
+            .as_ref()
+            .is_none_or(|agents| agents.iter().any(|key| agent.matches(key)))
+    }
+
+    pub fn interaction_limit(&self, global_limit: u32) -> u32 {
+        self.daily_limit.unwrap_or(global_limit)
+    }
+
+    pub fn use_onchain(&self, global_use_onchain: bool) -> bool {
+        self.use_onchain.unwrap_or(global_use_onchain)
+    }
+}
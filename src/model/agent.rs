@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::utils::{constants, helper::Helper};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Agent {
+    pub id: String,
+    pub name: String,
+    pub base_url: String,
+    pub enabled: bool,
+    pub daily_limit: Option<u32>,
+    pub messages: Vec<String>,
+    pub question_answers: Vec<(String, String)>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AgentDefinition {
+    name: Option<String>,
+    deployment_id: Option<String>,
+    base_url: Option<String>,
+    enabled: Option<bool>,
+    daily_limit: Option<u32>,
+    prompts: Option<Vec<String>>,
+    answers: Option<Vec<CannedAnswer>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CannedAnswer {
+    question: String,
+    answer: String,
+}
+
+impl Agent {
+    pub fn new(id: &str, name: &str, messages: &[&str], question_answers: &[(&str, &str)]) -> Self {
+        Agent {
+            id: id.to_string(),
+            name: name.to_string(),
+            base_url: constants::AGENT_BASE_URL.to_string(),
+            enabled: true,
+            daily_limit: None,
+            messages: messages.iter().map(|msg| msg.to_string()).collect(),
+            question_answers: question_answers
+                .iter()
+                .map(|(question, answer)| (question.to_string(), answer.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn endpoint(&self) -> String {
+        self.base_url
+            .replace("{deployment}", &self.id.to_lowercase().replace("_", "-"))
+    }
+
+    pub fn key(&self) -> String {
+        self.name.trim().to_uppercase().replace(' ', "_")
+    }
+
+    pub fn matches(&self, key: &str) -> bool {
+        let key = key.trim();
+        self.key().eq_ignore_ascii_case(key) || self.id.eq_ignore_ascii_case(key)
+    }
+
+    pub fn apply_daily_limits(agents: &mut [Agent], limits: &str) -> Result<(), String> {
+        for entry in limits.split(',').filter(|entry| !entry.trim().is_empty()) {
+            let (key, limit) = entry.split_once('=').ok_or(format!(
+                "Invalid agent limit '{}', expected NAME=LIMIT",
+                entry
+            ))?;
+            let limit: u32 = limit
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid limit '{}' for agent {}", limit.trim(), key))?;
+            let agent = agents
+                .iter_mut()
+                .find(|agent| agent.matches(key))
+                .ok_or(format!("Unknown agent '{}' in agent limits", key.trim()))?;
+            agent.daily_limit = Some(limit);
+        }
+        Ok(())
+    }
+
+    pub fn defaults() -> Vec<Agent> {
+        vec![
+            Agent::new(
+                constants::PROFESSOR_AGENT,
+                "Professor",
+                &constants::PROFFESOR_MESSAGE_LIST,
+                &constants::PROFFESOR_QUESTION_ANSWER_LIST,
+            ),
+            Agent::new(
+                constants::SHERLOCK,
+                "Sherlock",
+                &constants::SHERLOCK_MESSAGE_LIST,
+                &constants::SHERLOCK_QUESTION_ANSWER_LIST,
+            ),
+            Agent::new(
+                constants::CRYPTO_BUDDY,
+                "Crypto Buddy",
+                &constants::CRYPTO_BUDDY_MESSAGE_LIST,
+                &constants::CRYPTO_BUDDY_QUESTION_ANSWER_LIST,
+            ),
+        ]
+    }
+
+    pub fn load(path: &str, real_mode: bool) -> Result<Vec<Agent>, String> {
+        match Helper::read_optional(path)? {
+            Some(data) => Self::parse(&data, real_mode).map_err(|err| format!("{}: {}", path, err)),
+            None => Ok(Self::defaults()),
+        }
+    }
+
+    pub fn parse(data: &str, real_mode: bool) -> Result<Vec<Agent>, String> {
+        let definitions: Vec<AgentDefinition> =
+            serde_json::from_str(data).map_err(|err| format!("Invalid agents file: {}", err))?;
+        let defaults = Self::defaults();
+
+        let mut agents = vec![];
+        for (idx, definition) in definitions.into_iter().enumerate() {
+            let position = idx + 1;
+            let id = definition
+                .deployment_id
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .ok_or(format!("Agent #{} is missing deployment_id", position))?;
+            if id.contains(char::is_whitespace) {
+                return Err(format!(
+                    "Agent #{} has invalid deployment_id '{}'",
+                    position, id
+                ));
+            }
+
+            let fallback = defaults.iter().find(|agent| agent.id == id);
+            let name = definition
+                .name
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .or(fallback.map(|agent| agent.name.to_owned()))
+                .ok_or(format!("Agent #{} ({}) is missing name", position, id))?;
+
+            let base_url = definition
+                .base_url
+                .unwrap_or(constants::AGENT_BASE_URL.to_string());
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                return Err(format!(
+                    "Agent #{} ({}) has invalid base_url '{}', expected http(s) url",
+                    position, name, base_url
+                ));
+            }
+
+            let messages = definition
+                .prompts
+                .or(fallback.map(|agent| agent.messages.to_owned()))
+                .unwrap_or_default();
+            let question_answers = match definition.answers {
+                Some(answers) => answers
+                    .into_iter()
+                    .map(|canned| (canned.question, canned.answer))
+                    .collect(),
+                None => fallback
+                    .map(|agent| agent.question_answers.to_owned())
+                    .unwrap_or_default(),
+            };
+
+            let agent = Agent {
+                id,
+                name,
+                base_url,
+                enabled: definition.enabled.unwrap_or(true),
+                daily_limit: definition.daily_limit,
+                messages,
+                question_answers,
+            };
+
+            if agent.enabled {
+                if agent.messages.iter().all(|msg| msg.trim().is_empty()) {
+                    return Err(format!(
+                        "Agent #{} ({}) has no prompts",
+                        position, agent.name
+                    ));
+                }
+                if !real_mode && agent.question_answers.is_empty() {
+                    return Err(format!(
+                        "Agent #{} ({}) has no canned answers",
+                        position, agent.name
+                    ));
+                }
+            }
+
+            agents.push(agent);
+        }
+
+        let mut seen = HashSet::new();
+        for agent in agents.iter() {
+            if !seen.insert(agent.id.to_owned()) {
+                return Err(format!("Duplicate agent deployment_id '{}'", agent.id));
+            }
+        }
+
+        if !agents.iter().any(|agent| agent.enabled) {
+            return Err("No enabled agent found".to_string());
+        }
+
+        Ok(agents)
+    }
+}
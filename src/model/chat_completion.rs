@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatCompletion {
+    pub choices: Vec<ChatChoice>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatChoice {
+    pub message: ChatMessage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatMessage {
+    #[serde(default)]
+    pub role: Option<String>,
+    pub content: String,
+}
+
+impl ChatCompletion {
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .map(|choice| choice.message.content.as_str())
+            .filter(|content| !content.trim().is_empty())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatCompletionChunk {
+    pub choices: Vec<ChatChunkChoice>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatChunkChoice {
+    #[serde(default)]
+    pub delta: ChatDelta,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ChatDelta {
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+impl ChatCompletionChunk {
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.delta.content.as_deref())
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub struct DbError {
+    pub message: String,
+}
+
+impl DbError {
+    pub fn new(message: &str) -> Self {
+        DbError {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Database Error: {}", self.message)
+    }
+}
+
+impl std::error::Error for DbError {}
+
+impl From<rusqlite::Error> for DbError {
+    fn from(error: rusqlite::Error) -> Self {
+        DbError {
+            message: format!("{}", error),
+        }
+    }
+}
+
+impl From<String> for DbError {
+    fn from(message: String) -> Self {
+        DbError { message }
+    }
+}
+
+impl From<&str> for DbError {
+    fn from(message: &str) -> Self {
+        DbError {
+            message: message.to_string(),
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod api_error;
+pub mod db_error;
 pub mod operation_error;
 pub mod setup_error;
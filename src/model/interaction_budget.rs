@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+use super::agent::Agent;
+
+#[derive(Debug, Clone, Default)]
+pub struct InteractionBudget {
+    pub global_used: u32,
+    pub global_limit: u32,
+    pub agent_used: HashMap<String, u32>,
+}
+
+impl InteractionBudget {
+    pub fn global_remaining(&self) -> u32 {
+        self.global_limit.saturating_sub(self.global_used)
+    }
+
+    pub fn used_by(&self, agent: &Agent) -> u32 {
+        self.agent_used.get(&agent.id).copied().unwrap_or(0)
+    }
+
+    pub fn remaining_for(&self, agent: &Agent) -> u32 {
+        let global_remaining = self.global_remaining();
+        match agent.daily_limit {
+            Some(limit) => limit
+                .saturating_sub(self.used_by(agent))
+                .min(global_remaining),
+            None => global_remaining,
+        }
+    }
+
+    pub fn is_exhausted(&self, agents: &[&Agent]) -> bool {
+        !agents.is_empty() && agents.iter().all(|agent| self.remaining_for(agent) == 0)
+    }
+}
@@ -1,4 +1,13 @@
+pub mod account;
+pub mod account_context;
+pub mod agent;
 pub mod api_response;
+pub mod chat_completion;
 pub mod exception;
+pub mod interaction_budget;
+pub mod onchain_action;
 pub mod spinner_data;
+pub mod token_balance;
+pub mod usage_report;
 pub mod user_balance;
+pub mod user_stats;
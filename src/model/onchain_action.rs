@@ -0,0 +1,255 @@
+use ethers::{
+    abi::{
+        token::{LenientTokenizer, Tokenizer},
+        HumanReadableParser, Token,
+    },
+    types::{Bytes, H160},
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::utils::helper::Helper;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActionKind {
+    SelfTransfer,
+    WalletTransfer {
+        to: String,
+    },
+    TokenTransfer {
+        token: Option<String>,
+        to: Option<String>,
+    },
+    ContractCall {
+        to: String,
+        function: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    RawCalldata {
+        to: String,
+        data: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OnchainAction {
+    pub name: String,
+    pub kind: ActionKind,
+    pub amount: Decimal,
+    pub daily_count: Option<u32>,
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ActionPlan {
+    pub default: Vec<OnchainAction>,
+    pub accounts: HashMap<String, Vec<OnchainAction>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ActionPlanDefinition {
+    default: Option<Vec<OnchainActionDefinition>>,
+    accounts: Option<HashMap<String, Vec<OnchainActionDefinition>>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OnchainActionDefinition {
+    name: Option<String>,
+    #[serde(flatten)]
+    kind: ActionKind,
+    amount: Option<Decimal>,
+    daily_count: Option<u32>,
+    enabled: Option<bool>,
+}
+
+impl ActionKind {
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            ActionKind::SelfTransfer => None,
+            ActionKind::WalletTransfer { to } => Some(to),
+            ActionKind::TokenTransfer { to, .. } => to.as_deref(),
+            ActionKind::ContractCall { to, .. } => Some(to),
+            ActionKind::RawCalldata { to, .. } => Some(to),
+        }
+    }
+
+    pub fn account_ref(target: &str) -> Option<usize> {
+        target
+            .trim()
+            .strip_prefix("account:")
+            .and_then(|number| number.parse::<usize>().ok())
+            .filter(|number| *number > 0)
+    }
+
+    pub fn calldata(&self) -> Result<Option<Bytes>, String> {
+        match self {
+            ActionKind::ContractCall { function, args, .. } => {
+                let signature = function.trim();
+                let signature = if signature.starts_with("function ") {
+                    signature.to_string()
+                } else {
+                    format!("function {}", signature)
+                };
+                let function = HumanReadableParser::parse_function(&signature)
+                    .map_err(|err| format!("Invalid function '{}': {}", signature, err))?;
+                if function.inputs.len() != args.len() {
+                    return Err(format!(
+                        "Function {} expects {} args, got {}",
+                        function.name,
+                        function.inputs.len(),
+                        args.len()
+                    ));
+                }
+
+                let tokens = function
+                    .inputs
+                    .iter()
+                    .zip(args.iter())
+                    .map(|(param, arg)| {
+                        LenientTokenizer::tokenize(&param.kind, arg).map_err(|err| {
+                            format!("Invalid arg '{}' for {}: {}", arg, param.kind, err)
+                        })
+                    })
+                    .collect::<Result<Vec<Token>, String>>()?;
+                let data = function
+                    .encode_input(&tokens)
+                    .map_err(|err| format!("Failed to encode {}: {}", function.name, err))?;
+                Ok(Some(data.into()))
+            }
+            ActionKind::RawCalldata { data, .. } => Bytes::from_str(data.trim())
+                .map(Some)
+                .map_err(|err| format!("Invalid calldata '{}': {}", data, err)),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl OnchainAction {
+    pub fn new(name: &str, kind: ActionKind, amount: Decimal) -> Self {
+        OnchainAction {
+            name: name.to_string(),
+            kind,
+            amount,
+            daily_count: None,
+            enabled: true,
+        }
+    }
+
+    pub fn log_type(&self) -> String {
+        format!("onchain:{}", self.name)
+    }
+}
+
+impl ActionPlan {
+    pub fn new(default: Vec<OnchainAction>) -> Self {
+        ActionPlan {
+            default,
+            accounts: HashMap::new(),
+        }
+    }
+
+    pub fn for_account(&self, account_number: usize, address: &str) -> &[OnchainAction] {
+        self.accounts
+            .get(&account_number.to_string())
+            .or(self.accounts.get(&address.to_lowercase()))
+            .unwrap_or(&self.default)
+    }
+
+    pub fn load(path: &str, fallback: ActionPlan) -> Result<ActionPlan, String> {
+        match Helper::read_optional(path)? {
+            Some(data) => Self::parse(&data).map_err(|err| format!("{}: {}", path, err)),
+            None => Ok(fallback),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<ActionPlan, String> {
+        let definition: ActionPlanDefinition =
+            serde_json::from_str(data).map_err(|err| format!("Invalid actions file: {}", err))?;
+
+        let default = Self::parse_actions("default", definition.default.unwrap_or_default())?;
+        let mut accounts = HashMap::new();
+        for (key, actions) in definition.accounts.unwrap_or_default() {
+            let key = key.trim().to_lowercase();
+            let is_number = key.parse::<usize>().is_ok_and(|number| number > 0);
+            if !is_number && H160::from_str(&key).is_err() {
+                return Err(format!(
+                    "Invalid account key '{}', expected account number or address",
+                    key
+                ));
+            }
+            let actions = Self::parse_actions(&key, actions)?;
+            accounts.insert(key, actions);
+        }
+
+        Ok(ActionPlan { default, accounts })
+    }
+
+    fn parse_actions(
+        owner: &str,
+        definitions: Vec<OnchainActionDefinition>,
+    ) -> Result<Vec<OnchainAction>, String> {
+        let mut actions = vec![];
+        for (idx, definition) in definitions.into_iter().enumerate() {
+            let position = idx + 1;
+            let name = definition
+                .name
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .ok_or(format!("Action #{} of {} is missing name", position, owner))?;
+
+            let amount = definition.amount.unwrap_or(Decimal::ZERO);
+            if amount.is_sign_negative() {
+                return Err(format!("Action {} of {} has negative amount", name, owner));
+            }
+            if let Some(target) = definition.kind.target() {
+                if ActionKind::account_ref(target).is_none() && H160::from_str(target).is_err() {
+                    return Err(format!(
+                        "Action {} of {} has invalid target '{}', expected address or account:<number>",
+                        name, owner, target
+                    ));
+                }
+            }
+            if let ActionKind::TokenTransfer {
+                token: Some(token), ..
+            } = &definition.kind
+            {
+                if H160::from_str(token).is_err() {
+                    return Err(format!(
+                        "Action {} of {} has invalid token address '{}'",
+                        name, owner, token
+                    ));
+                }
+            }
+            definition
+                .kind
+                .calldata()
+                .map_err(|err| format!("Action {} of {}: {}", name, owner, err))?;
+
+            actions.push(OnchainAction {
+                name,
+                kind: definition.kind,
+                amount,
+                daily_count: definition.daily_count,
+                enabled: definition.enabled.unwrap_or(true),
+            });
+        }
+
+        let mut seen = HashSet::new();
+        for action in actions.iter() {
+            if !seen.insert(action.name.to_lowercase()) {
+                return Err(format!(
+                    "Duplicate action name '{}' in {}",
+                    action.name, owner
+                ));
+            }
+        }
+
+        Ok(actions)
+    }
+}
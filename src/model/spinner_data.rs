@@ -1,72 +1,83 @@
 use dashmap::DashMap;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::OnceCell;
 
-use super::user_balance::UserBalance;
+use super::{
+    interaction_budget::InteractionBudget, token_balance::TokenBalance, user_balance::UserBalance,
+    user_stats::UserStats,
+};
 
-static SPINNER_DATA_MAP: OnceCell<Arc<DashMap<String, SpinnerData>>> = OnceCell::const_new();
+static SPINNER_DATA_MAP: OnceCell<Arc<DashMap<usize, SpinnerData>>> = OnceCell::const_new();
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SpinnerData {
     pub address: String,
-    pub stats: Value,
+    pub stats: Option<UserStats>,
     pub balance: UserBalance,
+    pub tokens: Vec<TokenBalance>,
+    #[serde(skip)]
+    pub budget: Option<InteractionBudget>,
+    pub actions_today: HashMap<String, usize>,
 }
 
-impl SpinnerData {
-    pub async fn init() {
-        SPINNER_DATA_MAP.set(Arc::new(DashMap::new())).unwrap();
-    }
-
-    pub fn default() -> SpinnerData {
+impl Default for SpinnerData {
+    fn default() -> Self {
         SpinnerData {
             address: "".to_string(),
-            stats: json!({}),
+            stats: None,
             balance: UserBalance {
                 gas: Decimal::ZERO,
                 symbol: String::new(),
             },
+            tokens: vec![],
+            budget: None,
+            actions_today: HashMap::new(),
         }
     }
+}
+
+impl SpinnerData {
+    pub async fn init() {
+        SPINNER_DATA_MAP.set(Arc::new(DashMap::new())).unwrap();
+    }
 
-    fn storage() -> &'static Arc<DashMap<String, SpinnerData>> {
+    fn storage() -> &'static Arc<DashMap<usize, SpinnerData>> {
         SPINNER_DATA_MAP
             .get()
             .expect("SPINNER_DATA_MAP not initialized")
     }
 
-    pub fn get_or_create(key: &str) -> SpinnerData {
+    pub fn get_or_create(key: usize) -> SpinnerData {
         let storage = Self::storage();
 
-        if let Some(existing) = storage.get(key) {
+        if let Some(existing) = storage.get(&key) {
             return existing.clone();
         }
 
         let new_data = SpinnerData::default();
 
-        storage.insert(key.to_string(), new_data.clone());
+        storage.insert(key, new_data.clone());
         new_data
     }
 
-    pub fn get(key: &str) -> Option<SpinnerData> {
-        Self::storage().get(key).map(|entry| entry.clone())
+    pub fn get(key: usize) -> Option<SpinnerData> {
+        Self::storage().get(&key).map(|entry| entry.clone())
     }
 
-    pub fn update<F>(key: &str, updater: F)
+    pub fn update<F>(key: usize, updater: F)
     where
         F: FnOnce(&mut SpinnerData),
     {
         let storage = Self::storage();
 
-        if let Some(mut entry) = storage.get_mut(key) {
+        if let Some(mut entry) = storage.get_mut(&key) {
             updater(&mut entry);
         } else {
             let mut new_data = SpinnerData::default();
             updater(&mut new_data);
-            storage.insert(key.to_string(), new_data);
+            storage.insert(key, new_data);
         }
     }
 }
@@ -0,0 +1,46 @@
+use ethers::{
+    types::U256,
+    utils::{format_units, parse_units},
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenBalance {
+    pub address: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub balance: Decimal,
+}
+
+impl TokenBalance {
+    pub fn new(address: &str, symbol: String, decimals: u8, balance: U256) -> Result<Self, String> {
+        let formatted = format_units(balance, decimals as u32)
+            .map_err(|e| format!("Invalid {} balance {}: {}", symbol, balance, e))?;
+        let balance = formatted.parse::<Decimal>().map_err(|_| {
+            format!(
+                "{} balance {} ({}) cannot be represented",
+                symbol, formatted, address
+            )
+        })?;
+
+        Ok(TokenBalance {
+            address: address.to_owned(),
+            symbol,
+            decimals,
+            balance,
+        })
+    }
+
+    pub fn raw_amount(&self, amount: Decimal) -> Result<U256, String> {
+        if amount.normalize().scale() > self.decimals as u32 {
+            return Err(format!(
+                "Invalid {} amount {}, token only has {} decimals",
+                self.symbol, amount, self.decimals
+            ));
+        }
+        parse_units(amount.to_string(), self.decimals as u32)
+            .map(Into::into)
+            .map_err(|e| format!("Invalid {} amount {}: {}", self.symbol, amount, e))
+    }
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsageReport {
+    pub message: String,
+    #[serde(default)]
+    pub interaction_id: Option<String>,
+}
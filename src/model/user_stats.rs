@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserStats {
+    pub total_interactions: u64,
+    #[serde(default)]
+    pub total_agents_used: Option<u64>,
+    #[serde(default)]
+    pub first_seen: Option<String>,
+    #[serde(default)]
+    pub last_active: Option<String>,
+}
@@ -1,42 +1,44 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use crate::{
     model::{
+        account_context::AccountContext,
+        agent::Agent,
+        chat_completion::ChatCompletion,
         exception::{api_error::ApiError, setup_error::SetupError},
         spinner_data::SpinnerData,
+        usage_report::UsageReport,
+        user_stats::UserStats,
     },
     service::{
         api::api_service::{ApiService, HttpMethod},
-        db::rustqlite::RustQLite,
+        db::rustqlite::{NewInteraction, RustQLite},
     },
     utils::{
-        configuration::Config, constants, exception_handler::ExceptionHandler, helper::Helper,
+        configuration::Config, exception_handler::ExceptionHandler, helper::Helper,
         spinner::Spinner,
     },
 };
+use log::info;
 use reqwest::StatusCode;
-use serde_json::json;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+const STREAM_PREVIEW_CHARS: usize = 200;
 
 pub struct ApiRepository {
-    pub acc: Arc<String>,
+    pub acc: Arc<AccountContext>,
     pub api_service: ApiService,
+    pub db: Arc<RustQLite>,
 }
 
 impl ApiRepository {
-    pub fn new(acc: &Arc<String>) -> Result<Self, SetupError> {
-        let acc_idx = Helper::get_data_index_from_file(acc, "accounts.json").unwrap_or(0);
-        let proxy_list = Helper::read_data_from_file("proxy_list.json").unwrap_or(vec![]);
-
-        let proxy = if let Some(proxy) = proxy_list.get(acc_idx as usize) {
-            Some(proxy)
-        } else {
-            None
-        };
-
-        match ApiService::new(proxy.cloned(), None) {
+    pub fn new(acc: &Arc<AccountContext>, db: &Arc<RustQLite>) -> Result<Self, SetupError> {
+        match ApiService::new(acc.proxy.to_owned(), None) {
             Ok(api_service) => Ok(ApiRepository {
                 acc: Arc::clone(acc),
-                api_service,
+                api_service: api_service.with_retry_policy(Config::get().retry_policy.clone()),
+                db: Arc::clone(db),
             }),
             Err(err) => Err(err),
         }
@@ -51,7 +53,7 @@ impl ApiRepository {
 
         match self
             .api_service
-            .fetch(
+            .fetch_json::<UserStats>(
                 &format!(
                     "https://quests-usage-dev.prod.zettablock.com/api/user/{}/stats",
                     address
@@ -63,149 +65,63 @@ impl ApiRepository {
             )
             .await
         {
-            Ok(res) if res.status.is_success() => {
+            Ok(stats) => {
                 Spinner::log(&self.acc, "Successfully retrieved user stats.", 1000).await;
-                let stats = res.data;
-                SpinnerData::update(&self.acc, |data| data.stats = stats.clone());
-            }
-            Ok(res) => {
-                let error = ExceptionHandler::create_api_eror(res);
-                ExceptionHandler::api_error(&self.acc, error).await;
-            }
-            Err(e) => {
-                let error = ApiError {
-                    code: StatusCode::INTERNAL_SERVER_ERROR,
-                    message: format!("Request Failed: {}", e),
-                };
-                ExceptionHandler::api_error(&self.acc, error).await;
+                SpinnerData::update(self.acc.index, |data| data.stats = Some(stats));
             }
+            Err(error) => ExceptionHandler::api_error(&self.acc, error).await,
         }
     }
 
-    pub async fn chat_with_professor_agent(&self, address: &String) {
-        Spinner::log(&self.acc, "Chatting With Professor Agent", 1000).await;
+    pub async fn chat_with_agent(&self, agent: &Agent, address: &String) {
+        Spinner::log(
+            &self.acc,
+            &format!("Chatting With {} Agent", agent.name),
+            1000,
+        )
+        .await;
+
+        let asked_today = match self.db.get_requests_today(address, &agent.id).await {
+            Ok(requests) => requests,
+            Err(error) => {
+                ExceptionHandler::db_error(&self.acc, error).await;
+                vec![]
+            }
+        };
 
         if Config::get().real_mode {
-            let message = Helper::pick_random_from_arr(&constants::PROFFESOR_MESSAGE_LIST);
-            let body = Some(json!({
-                "message": message,
-                "stream": false
-            }));
-            Spinner::log(
-                &self.acc,
-                &format!("Sending Message {} to Professor Agent", message.unwrap()).to_string(),
-                3000,
-            )
-            .await;
-
-            Spinner::log(
-                &self.acc,
-                &format!(
-                    "Message {} Send to Professor Agent \n \nAwaiting For Agent To Response",
-                    message.unwrap()
-                )
-                .to_string(),
-                1000,
-            )
-            .await;
-            match self
-                .api_service
-                .fetch(
-                    &format!(
-                        "https://{}.stag-vxzy.zettablock.com/main",
-                        constants::PROFESSOR_AGENT.to_lowercase().replace("_", "-")
-                    )
-                    .as_str(),
-                    Some(HttpMethod::POST),
-                    body,
-                    None,
-                    None,
-                )
-                .await
-            {
-                Ok(res) if res.status.is_success() => {
-                    let ai_res = res.data;
-                    let response_message = ai_res["choices"][0]["message"]["content"]
-                        .as_str()
-                        .unwrap_or_default();
-
+            let fresh_messages: Vec<String> = agent
+                .messages
+                .iter()
+                .filter(|message| !asked_today.contains(message))
+                .cloned()
+                .collect();
+            let messages = if fresh_messages.is_empty() {
+                &agent.messages
+            } else {
+                &fresh_messages
+            };
+
+            let message = match Helper::pick_random_from_arr(messages) {
+                Some(message) => message,
+                None => {
                     Spinner::log(
                         &self.acc,
-                        &format!(
-                            "Receiving Message {} from Professor Agent",
-                            response_message
-                        )
-                        .to_string(),
+                        &format!("No Message Available For {} Agent", agent.name),
                         3000,
                     )
                     .await;
-
-                    self.report_usage_onchain(
-                        address,
-                        constants::PROFESSOR_AGENT,
-                        message.unwrap(),
-                        response_message,
-                    )
-                    .await;
-                }
-                Ok(res) => {
-                    let error = ExceptionHandler::create_api_eror(res);
-                    ExceptionHandler::api_error(&self.acc, error).await;
+                    return;
                 }
-                Err(e) => {
-                    let error = ApiError {
-                        code: StatusCode::INTERNAL_SERVER_ERROR,
-                        message: format!("Request Failed: {}", e),
-                    };
-                    ExceptionHandler::api_error(&self.acc, error).await;
-                }
-            }
-        } else {
-            let (question, answer) =
-                Helper::pick_random_set(&constants::PROFFESOR_QUESTION_ANSWER_LIST);
-
-            Spinner::log(
-                &self.acc,
-                &format!("Sending Message {} to Professor Agent", question).to_string(),
-                3000,
-            )
-            .await;
-
-            Spinner::log(
-                &self.acc,
-                &format!(
-                    "Message {} Send to Professor Agent \n \nAwaiting For Agent To Response",
-                    question,
-                )
-                .to_string(),
-                1000,
-            )
-            .await;
-
-            Spinner::log(
-                &self.acc,
-                &format!("Receiving Message {} from Professor Agent", answer).to_string(),
-                3000,
-            )
-            .await;
-
-            self.report_usage_onchain(address, constants::PROFESSOR_AGENT, question, answer)
-                .await;
-        }
-    }
-
-    pub async fn chat_with_buddy_agent(&self, address: &String) {
-        Spinner::log(&self.acc, "Chatting With Crypto Buddy Agent", 1000).await;
-
-        if Config::get().real_mode {
-            let message = Helper::pick_random_from_arr(&constants::CRYPTO_BUDDY_MESSAGE_LIST);
+            };
+            let stream_mode = Config::get().stream_mode;
             let body = Some(json!({
                 "message": message,
-                "stream": false
+                "stream": stream_mode
             }));
             Spinner::log(
                 &self.acc,
-                &format!("Sending Message {} to Crypto Buddy Agent", message.unwrap()).to_string(),
+                &format!("Sending Message {} to {} Agent", message, agent.name),
                 3000,
             )
             .await;
@@ -213,72 +129,122 @@ impl ApiRepository {
             Spinner::log(
                 &self.acc,
                 &format!(
-                    "Message {} Send to Crypto Buddy Agent \n \nAwaiting For Agent To Response",
-                    message.unwrap()
-                )
-                .to_string(),
+                    "Message {} Send to {} Agent \n \nAwaiting For Agent To Response",
+                    message, agent.name
+                ),
                 1000,
             )
             .await;
-            match self
-                .api_service
-                .fetch(
-                    &format!(
-                        "https://{}.stag-vxzy.zettablock.com/main",
-                        constants::CRYPTO_BUDDY.to_lowercase().replace("_", "-")
+            let started_at = Instant::now();
+            let response = if stream_mode {
+                self.stream_agent_response(agent, body).await
+            } else {
+                self.api_service
+                    .fetch_json::<ChatCompletion>(
+                        agent.endpoint().as_str(),
+                        Some(HttpMethod::POST),
+                        body,
+                        None,
+                        None,
                     )
-                    .as_str(),
-                    Some(HttpMethod::POST),
-                    body,
-                    None,
-                    None,
-                )
-                .await
-            {
-                Ok(res) if res.status.is_success() => {
-                    let ai_res = res.data;
-                    let response_message = ai_res["choices"][0]["message"]["content"]
-                        .as_str()
-                        .unwrap_or_default();
+                    .await
+                    .map(|completion| completion.content().unwrap_or_default().to_string())
+            };
+
+            let latency_ms = started_at.elapsed().as_millis() as u64;
 
+            match response {
+                Ok(response_message) if response_message.trim().is_empty() => {
+                    let error = ApiError {
+                        code: StatusCode::UNPROCESSABLE_ENTITY,
+                        message: format!("{} Agent returned an empty answer", agent.name),
+                    };
+                    if let Err(db_error) = self
+                        .db
+                        .insert_interaction(NewInteraction {
+                            address,
+                            agent_id: &agent.id,
+                            request_text: &message,
+                            response_text: Some(&response_message),
+                            http_status: Some(error.code.as_u16()),
+                            latency_ms: Some(latency_ms),
+                            report_status: "skipped",
+                        })
+                        .await
+                    {
+                        ExceptionHandler::db_error(&self.acc, db_error).await;
+                    }
+                    ExceptionHandler::api_error(&self.acc, error).await;
+                }
+                Ok(response_message) => {
                     Spinner::log(
                         &self.acc,
                         &format!(
-                            "Receiving Message {} from Crypto Buddy Agent",
-                            response_message
-                        )
-                        .to_string(),
+                            "Receiving Message {} from {} Agent",
+                            response_message, agent.name
+                        ),
                         3000,
                     )
                     .await;
 
-                    self.report_usage_onchain(
+                    self.report_interaction(
                         address,
-                        constants::CRYPTO_BUDDY,
-                        message.unwrap(),
-                        response_message,
+                        agent,
+                        &message,
+                        &response_message,
+                        Some(StatusCode::OK.as_u16()),
+                        Some(latency_ms),
                     )
                     .await;
                 }
-                Ok(res) => {
-                    let error = ExceptionHandler::create_api_eror(res);
-                    ExceptionHandler::api_error(&self.acc, error).await;
-                }
-                Err(e) => {
-                    let error = ApiError {
-                        code: StatusCode::INTERNAL_SERVER_ERROR,
-                        message: format!("Request Failed: {}", e),
-                    };
+                Err(error) => {
+                    if let Err(db_error) = self
+                        .db
+                        .insert_interaction(NewInteraction {
+                            address,
+                            agent_id: &agent.id,
+                            request_text: &message,
+                            response_text: None,
+                            http_status: Some(error.code.as_u16()),
+                            latency_ms: Some(latency_ms),
+                            report_status: "skipped",
+                        })
+                        .await
+                    {
+                        ExceptionHandler::db_error(&self.acc, db_error).await;
+                    }
                     ExceptionHandler::api_error(&self.acc, error).await;
                 }
             }
         } else {
-            let (question, answer) =
-                Helper::pick_random_set(&constants::CRYPTO_BUDDY_QUESTION_ANSWER_LIST);
+            let fresh_question_answers: Vec<(String, String)> = agent
+                .question_answers
+                .iter()
+                .filter(|(question, _)| !asked_today.contains(question))
+                .cloned()
+                .collect();
+            let question_answers = if fresh_question_answers.is_empty() {
+                &agent.question_answers
+            } else {
+                &fresh_question_answers
+            };
+
+            let (question, answer) = match Helper::pick_random_from_arr(question_answers) {
+                Some(question_answer) => question_answer,
+                None => {
+                    Spinner::log(
+                        &self.acc,
+                        &format!("No Question Available For {} Agent", agent.name),
+                        3000,
+                    )
+                    .await;
+                    return;
+                }
+            };
 
             Spinner::log(
                 &self.acc,
-                &format!("Sending Message {} to Crypto Buddy Agent", question).to_string(),
+                &format!("Sending Message {} to {} Agent", question, agent.name),
                 3000,
             )
             .await;
@@ -286,133 +252,115 @@ impl ApiRepository {
             Spinner::log(
                 &self.acc,
                 &format!(
-                    "Message {} Send to Crypto Buddy Agent \n \nAwaiting For Agent To Response",
-                    question,
-                )
-                .to_string(),
+                    "Message {} Send to {} Agent \n \nAwaiting For Agent To Response",
+                    question, agent.name
+                ),
                 1000,
             )
             .await;
 
             Spinner::log(
                 &self.acc,
-                &format!("Receiving Message {} from Crypto Buddy Agent", answer).to_string(),
+                &format!("Receiving Message {} from {} Agent", answer, agent.name),
                 3000,
             )
             .await;
 
-            self.report_usage_onchain(address, constants::CRYPTO_BUDDY, question, answer)
+            self.report_interaction(address, agent, &question, &answer, None, None)
                 .await;
         }
     }
 
-    pub async fn chat_with_sherlock_agent(&self, address: &String) {
-        Spinner::log(&self.acc, "Chatting With Sherlock Agent", 1000).await;
-
-        if Config::get().real_mode {
-            let message = Helper::pick_random_from_arr(&constants::SHERLOCK_MESSAGE_LIST);
-            let body = Some(json!({
-                "message": message,
-                "stream": false
-            }));
-            Spinner::log(
-                &self.acc,
-                &format!("Sending Message {} to Sherlock Agent", message.unwrap()).to_string(),
-                3000,
-            )
-            .await;
+    async fn report_interaction(
+        &self,
+        address: &String,
+        agent: &Agent,
+        request: &str,
+        response: &str,
+        http_status: Option<u16>,
+        latency_ms: Option<u64>,
+    ) {
+        let interaction_id = match self
+            .db
+            .insert_interaction(NewInteraction {
+                address,
+                agent_id: &agent.id,
+                request_text: request,
+                response_text: Some(response),
+                http_status,
+                latency_ms,
+                report_status: "pending",
+            })
+            .await
+        {
+            Ok(id) => Some(id),
+            Err(error) => {
+                ExceptionHandler::db_error(&self.acc, error).await;
+                None
+            }
+        };
 
-            Spinner::log(
-                &self.acc,
-                &format!(
-                    "Message {} Send to Sherlock Agent \n \nAwaiting For Agent To Response",
-                    message.unwrap()
-                )
-                .to_string(),
-                1000,
-            )
-            .await;
-            match self
-                .api_service
-                .fetch(
-                    &format!(
-                        "https://{}.stag-vxzy.zettablock.com/main",
-                        constants::SHERLOCK.to_lowercase().replace("_", "-")
-                    )
-                    .as_str(),
-                    Some(HttpMethod::POST),
-                    body,
-                    None,
-                    None,
-                )
+        let report_status = if self
+            .report_usage_onchain(address, &agent.id, request, response)
+            .await
+        {
+            "reported"
+        } else {
+            "failed"
+        };
+        if let Some(interaction_id) = interaction_id {
+            if let Err(error) = self
+                .db
+                .update_interaction_report(interaction_id, report_status)
                 .await
             {
-                Ok(res) if res.status.is_success() => {
-                    let ai_res = res.data;
-                    let response_message = ai_res["choices"][0]["message"]["content"]
-                        .as_str()
-                        .unwrap_or_default();
-
-                    Spinner::log(
-                        &self.acc,
-                        &format!("Receiving Message {} from Sherlock Agent", response_message)
-                            .to_string(),
-                        3000,
-                    )
-                    .await;
-
-                    self.report_usage_onchain(
-                        address,
-                        constants::SHERLOCK,
-                        message.unwrap(),
-                        response_message,
-                    )
-                    .await;
-                }
-                Ok(res) => {
-                    let error = ExceptionHandler::create_api_eror(res);
-                    ExceptionHandler::api_error(&self.acc, error).await;
-                }
-                Err(e) => {
-                    let error = ApiError {
-                        code: StatusCode::INTERNAL_SERVER_ERROR,
-                        message: format!("Request Failed: {}", e),
-                    };
-                    ExceptionHandler::api_error(&self.acc, error).await;
-                }
+                ExceptionHandler::db_error(&self.acc, error).await;
             }
-        } else {
-            let (question, answer) =
-                Helper::pick_random_set(&constants::SHERLOCK_QUESTION_ANSWER_LIST);
+        }
+    }
 
-            Spinner::log(
-                &self.acc,
-                &format!("Sending Message {} to Sherlock Agent", question).to_string(),
-                3000,
-            )
-            .await;
+    async fn stream_agent_response(
+        &self,
+        agent: &Agent,
+        body: Option<Value>,
+    ) -> Result<String, ApiError> {
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<String>();
+
+        let endpoint = agent.endpoint();
+        let request = self.api_service.fetch_stream(
+            endpoint.as_str(),
+            Some(HttpMethod::POST),
+            body,
+            None,
+            None,
+            Some(progress_tx),
+        );
+
+        let progress = async {
+            while let Some(mut partial) = progress_rx.recv().await {
+                while let Ok(latest) = progress_rx.try_recv() {
+                    partial = latest;
+                }
 
-            Spinner::log(
-                &self.acc,
-                &format!(
-                    "Message {} Send to Sherlock Agent \n \nAwaiting For Agent To Response",
-                    question,
+                let total_chars = partial.chars().count();
+                let preview: String = partial
+                    .chars()
+                    .skip(total_chars.saturating_sub(STREAM_PREVIEW_CHARS))
+                    .collect();
+                Spinner::log(
+                    &self.acc,
+                    &format!(
+                        "Receiving Message from {} Agent ({} chars)\n \n...{}",
+                        agent.name, total_chars, preview
+                    ),
+                    0,
                 )
-                .to_string(),
-                1000,
-            )
-            .await;
-
-            Spinner::log(
-                &self.acc,
-                &format!("Receiving Message {} from Sherlock Agent", answer).to_string(),
-                3000,
-            )
-            .await;
-
-            self.report_usage_onchain(address, constants::SHERLOCK, question, answer)
                 .await;
-        }
+            }
+        };
+
+        let (response, _) = tokio::join!(request, progress);
+        response
     }
 
     pub async fn report_usage_onchain(
@@ -421,7 +369,7 @@ impl ApiRepository {
         agent: &str,
         request: &str,
         response: &str,
-    ) {
+    ) -> bool {
         Spinner::log(&self.acc, "Reporting Onchain Usage...", 1000).await;
         let body = Some(json!({
             "wallet_address": address,
@@ -433,7 +381,7 @@ impl ApiRepository {
 
         match self
             .api_service
-            .fetch(
+            .fetch_json::<UsageReport>(
                 "https://quests-usage-dev.prod.zettablock.com/api/report_usage",
                 Some(HttpMethod::POST),
                 body,
@@ -442,21 +390,18 @@ impl ApiRepository {
             )
             .await
         {
-            Ok(res) if res.status.is_success() => {
+            Ok(report) => {
+                info!("Usage Report : {:?}", report);
                 Spinner::log(&self.acc, "Successfully Report Onchain Ussage...", 1000).await;
                 self.get_user_stats(address).await;
-                RustQLite::insert_log(address, "interact").await;
-            }
-            Ok(res) => {
-                let error = ExceptionHandler::create_api_eror(res);
-                ExceptionHandler::api_error(&self.acc, error).await;
+                if let Err(error) = self.db.insert_log(address, "interact").await {
+                    ExceptionHandler::db_error(&self.acc, error).await;
+                }
+                true
             }
-            Err(e) => {
-                let error = ApiError {
-                    code: StatusCode::INTERNAL_SERVER_ERROR,
-                    message: format!("Request Failed: {}", e),
-                };
+            Err(error) => {
                 ExceptionHandler::api_error(&self.acc, error).await;
+                false
             }
         }
     }
@@ -1,17 +1,33 @@
+use super::{
+    retry_policy::RetryPolicy,
+    sse_parser::{SseEvent, SseParser},
+};
 use crate::{
-    model::{api_response::ApiResponse, exception::setup_error::SetupError},
-    utils::helper::Helper,
+    model::{
+        api_response::ApiResponse,
+        chat_completion::{ChatCompletion, ChatCompletionChunk},
+        exception::{api_error::ApiError, setup_error::SetupError},
+    },
+    utils::{exception_handler::ExceptionHandler, helper::Helper},
 };
-use log::{error, info};
+use log::{error, info, warn};
 use reqwest::{
     header::{
         HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, REFERER,
         USER_AGENT,
     },
-    Client, ClientBuilder, Proxy, StatusCode,
+    Client, ClientBuilder, Proxy, Response, StatusCode,
 };
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
-use std::{collections::HashMap, fmt::Debug, time::Duration};
+use std::{any::type_name, collections::HashMap, fmt::Debug, time::Duration};
+use tokio::{
+    sync::mpsc::UnboundedSender,
+    time::{sleep, timeout},
+};
+
+const STREAM_TIMEOUT_SECS: u64 = 600;
+const STREAM_IDLE_TIMEOUT_SECS: u64 = 60;
 
 #[derive(Debug)]
 pub enum HttpMethod {
@@ -21,9 +37,16 @@ pub enum HttpMethod {
     DELETE,
 }
 
+impl HttpMethod {
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(self, HttpMethod::POST)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ApiService {
     client: Client,
+    retry_policy: RetryPolicy,
 }
 
 impl ApiService {
@@ -71,17 +94,26 @@ impl ApiService {
                 .map_err(|e| SetupError::new(&format!("Api Client Builder Error: {}", e)))?,
         };
 
-        Ok(ApiService { client })
+        Ok(ApiService {
+            client,
+            retry_policy: RetryPolicy::default(),
+        })
     }
 
-    pub async fn fetch(
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    async fn send(
         &self,
         endpoint: &str,
         method: Option<HttpMethod>,
         body: Option<Value>,
         token: Option<&str>,
         additional_headers: Option<HashMap<String, String>>,
-    ) -> Result<ApiResponse, reqwest::Error> {
+        timeout: Option<Duration>,
+    ) -> Result<Response, reqwest::Error> {
         let method = method.unwrap_or(HttpMethod::GET);
         info!(
             "Send API Request\nEndpoint: {}\nMethod: {:?}\nBody: {:?}",
@@ -109,34 +141,96 @@ impl ApiService {
             }
         }
 
-        let request = match method {
-            HttpMethod::GET => self.client.get(endpoint),
-            HttpMethod::POST => self.client.post(endpoint),
-            HttpMethod::PUT => self.client.put(endpoint),
-            HttpMethod::DELETE => self.client.delete(endpoint),
-        };
+        let idempotent = method.is_idempotent();
+        let mut attempt = 1;
+        loop {
+            let request = match method {
+                HttpMethod::GET => self.client.get(endpoint),
+                HttpMethod::POST => self.client.post(endpoint),
+                HttpMethod::PUT => self.client.put(endpoint),
+                HttpMethod::DELETE => self.client.delete(endpoint),
+            };
 
-        let request = request.headers(headers);
+            let request = request.headers(headers.clone());
 
-        let request = if let Some(body) = body {
-            request.body(body.to_string())
-        } else {
-            request
-        };
+            let request = if let Some(timeout) = timeout {
+                request.timeout(timeout)
+            } else {
+                request
+            };
 
-        let response = match request.send().await {
-            Ok(res) => res,
-            Err(e) => {
-                error!("Request failed: {}", e);
-                return Err(e);
+            let request = if let Some(body) = &body {
+                request.body(body.to_string())
+            } else {
+                request
+            };
+
+            match request.send().await {
+                Ok(res)
+                    if self
+                        .retry_policy
+                        .is_retryable_status(res.status(), idempotent)
+                        && self.retry_policy.should_retry(attempt) =>
+                {
+                    let delay = self
+                        .retry_policy
+                        .delay(attempt, RetryPolicy::retry_after(res.headers()));
+                    warn!(
+                        "Attempt {}/{} received {}, retrying in {} ms",
+                        attempt,
+                        self.retry_policy.max_attempts,
+                        res.status(),
+                        delay.as_millis()
+                    );
+                    sleep(delay).await;
+                }
+                Ok(res) => {
+                    info!("Received response: {} (attempt {})", res.status(), attempt);
+                    return Ok(res);
+                }
+                Err(e)
+                    if self.retry_policy.is_retryable_error(&e, idempotent)
+                        && self.retry_policy.should_retry(attempt) =>
+                {
+                    let delay = self.retry_policy.delay(attempt, None);
+                    warn!(
+                        "Attempt {}/{} failed: {}, retrying in {} ms",
+                        attempt,
+                        self.retry_policy.max_attempts,
+                        e,
+                        delay.as_millis()
+                    );
+                    sleep(delay).await;
+                }
+                Err(e) => {
+                    error!("Request failed after {} attempt(s): {}", attempt, e);
+                    return Err(e);
+                }
             }
-        };
 
+            attempt += 1;
+        }
+    }
+
+    pub async fn fetch(
+        &self,
+        endpoint: &str,
+        method: Option<HttpMethod>,
+        body: Option<Value>,
+        token: Option<&str>,
+        additional_headers: Option<HashMap<String, String>>,
+    ) -> Result<ApiResponse, reqwest::Error> {
+        let response = self
+            .send(endpoint, method, body, token, additional_headers, None)
+            .await?;
+
+        Self::parse_response(response).await
+    }
+
+    async fn parse_response(response: Response) -> Result<ApiResponse, reqwest::Error> {
         let status = response.status();
         let status_text = status.canonical_reason().unwrap_or(status.as_str());
 
-        info!("Received response: {}", status);
-
         let content_type = response
             .headers()
             .get(CONTENT_TYPE)
@@ -179,4 +273,130 @@ impl ApiService {
 
         Ok(response_data)
     }
+
+    pub async fn fetch_json<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        method: Option<HttpMethod>,
+        body: Option<Value>,
+        token: Option<&str>,
+        additional_headers: Option<HashMap<String, String>>,
+    ) -> Result<T, ApiError> {
+        let res = self
+            .fetch(endpoint, method, body, token, additional_headers)
+            .await
+            .map_err(Self::request_error)?;
+
+        if !res.status.is_success() {
+            return Err(ExceptionHandler::create_api_eror(res));
+        }
+
+        serde_json::from_value::<T>(res.data).map_err(|e| Self::decode_error::<T>(res.status, e))
+    }
+
+    pub async fn fetch_stream(
+        &self,
+        endpoint: &str,
+        method: Option<HttpMethod>,
+        body: Option<Value>,
+        token: Option<&str>,
+        additional_headers: Option<HashMap<String, String>>,
+        progress: Option<UnboundedSender<String>>,
+    ) -> Result<String, ApiError> {
+        let mut additional_headers = additional_headers.unwrap_or_default();
+        additional_headers.insert(ACCEPT.to_string(), "text/event-stream".to_string());
+
+        let mut response = self
+            .send(
+                endpoint,
+                method,
+                body,
+                token,
+                Some(additional_headers),
+                Some(Duration::from_secs(STREAM_TIMEOUT_SECS)),
+            )
+            .await
+            .map_err(Self::request_error)?;
+
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|val| val.to_str().ok())
+            .is_some_and(|val| val.contains("text/event-stream"));
+
+        if !response.status().is_success() || !is_event_stream {
+            let res = Self::parse_response(response)
+                .await
+                .map_err(Self::request_error)?;
+            if !res.status.is_success() {
+                return Err(ExceptionHandler::create_api_eror(res));
+            }
+
+            let completion = serde_json::from_value::<ChatCompletion>(res.data)
+                .map_err(|e| Self::decode_error::<ChatCompletion>(res.status, e))?;
+            return Ok(completion.content().unwrap_or_default().to_string());
+        }
+
+        let status = response.status();
+        let mut parser = SseParser::new();
+        let mut message = String::new();
+
+        'stream: loop {
+            let chunk = timeout(
+                Duration::from_secs(STREAM_IDLE_TIMEOUT_SECS),
+                response.chunk(),
+            )
+            .await
+            .map_err(|_| ApiError {
+                code: StatusCode::GATEWAY_TIMEOUT,
+                message: "Stream stalled, no data received".to_string(),
+            })?
+            .map_err(Self::request_error)?;
+
+            let finished = chunk.is_none();
+            let events = match chunk {
+                Some(chunk) => parser.push(&chunk),
+                None => parser.finish(),
+            };
+
+            for event in events {
+                let data = match event {
+                    SseEvent::Data(data) => data,
+                    SseEvent::Done => break 'stream,
+                };
+
+                let event = serde_json::from_str::<ChatCompletionChunk>(&data)
+                    .map_err(|e| Self::decode_error::<ChatCompletionChunk>(status, e))?;
+                if let Some(content) = event.content() {
+                    message.push_str(content);
+                    if let Some(progress) = &progress {
+                        let _ = progress.send(message.to_owned());
+                    }
+                }
+            }
+
+            if finished {
+                break;
+            }
+        }
+
+        info!("Stream completed, received {} chars", message.len());
+        Ok(message)
+    }
+
+    fn request_error(error: reqwest::Error) -> ApiError {
+        ApiError {
+            code: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Request Failed: {}", error),
+        }
+    }
+
+    fn decode_error<T>(status: StatusCode, error: serde_json::Error) -> ApiError {
+        let type_name = type_name::<T>().rsplit("::").next().unwrap_or_default();
+        error!("Failed to decode {} response: {}", type_name, error);
+        ApiError {
+            code: status,
+            message: format!("Failed to decode {} response: {}", type_name, error),
+        }
+    }
 }
@@ -1 +1,3 @@
 pub mod api_service;
+pub mod retry_policy;
+pub mod sse_parser;
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub jitter: f64,
+    pub retryable_status: Vec<u16>,
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 1000,
+            max_delay_ms: 30000,
+            jitter: 0.2,
+            retryable_status: vec![408, 429, 500, 502, 503, 504],
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn should_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    pub fn is_retryable_status(&self, status: StatusCode, idempotent: bool) -> bool {
+        (idempotent || self.retry_non_idempotent)
+            && self.retryable_status.contains(&status.as_u16())
+    }
+
+    pub fn is_retryable_error(&self, error: &reqwest::Error, idempotent: bool) -> bool {
+        if idempotent || self.retry_non_idempotent {
+            error.is_connect() || error.is_timeout() || error.is_request()
+        } else {
+            error.is_connect()
+        }
+    }
+
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let max_delay = Duration::from_millis(self.max_delay_ms);
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .base_delay_ms
+            .saturating_mul(2u64.saturating_pow(exponent))
+            .min(self.max_delay_ms) as f64;
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            rand::rng().random_range((1.0 - jitter)..=(1.0 + jitter))
+        } else {
+            1.0
+        };
+
+        Duration::from_millis((backoff * factor) as u64).min(max_delay)
+    }
+
+    pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+        let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let date = DateTime::parse_from_rfc2822(value).ok()?;
+        (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+    }
+}
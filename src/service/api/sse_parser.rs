@@ -0,0 +1,53 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SseEvent {
+    Data(String),
+    Done,
+}
+
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        SseParser::default()
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        self.drain_lines()
+    }
+
+    pub fn finish(&mut self) -> Vec<SseEvent> {
+        if !self.buffer.is_empty() {
+            self.buffer.push(b'\n');
+        }
+        self.drain_lines()
+    }
+
+    fn drain_lines(&mut self) -> Vec<SseEvent> {
+        let mut events = vec![];
+        while let Some(pos) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            if self.done {
+                continue;
+            }
+
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim().strip_prefix("data:") else {
+                continue;
+            };
+
+            let data = data.trim();
+            if data == "[DONE]" {
+                self.done = true;
+                events.push(SseEvent::Done);
+            } else if !data.is_empty() {
+                events.push(SseEvent::Data(data.to_string()));
+            }
+        }
+        events
+    }
+}
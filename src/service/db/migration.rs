@@ -0,0 +1,95 @@
+use chrono::Utc;
+use rusqlite::{params, Connection, Result};
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_log",
+        sql: include_str!("migrations/0001_create_log.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "create_interactions",
+        sql: include_str!("migrations/0002_create_interactions.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "create_transactions",
+        sql: include_str!("migrations/0003_create_transactions.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "add_transaction_chain_id",
+        sql: include_str!("migrations/0004_add_transaction_chain_id.sql"),
+    },
+];
+
+pub fn latest_version() -> i64 {
+    MIGRATIONS
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn current_version(conn: &Connection) -> Result<i64> {
+    create_version_table(conn)?;
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )
+}
+
+pub fn migrate(conn: &mut Connection) -> Result<i64> {
+    apply(conn, MIGRATIONS)
+}
+
+pub fn apply(conn: &mut Connection, migrations: &[Migration]) -> Result<i64> {
+    let mut version = current_version(conn)?;
+
+    let mut pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|migration| migration.version > version)
+        .collect();
+    pending.sort_by_key(|migration| migration.version);
+
+    for migration in pending {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql)?;
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            params![
+                migration.version,
+                migration.name,
+                Utc::now()
+                    .naive_utc()
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            ],
+        )?;
+        tx.commit()?;
+
+        version = migration.version;
+    }
+
+    Ok(version)
+}
+
+fn create_version_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    address TEXT NOT NULL,
+    tx_type TEXT NOT NULL,
+    date TEXT NOT NULL
+);
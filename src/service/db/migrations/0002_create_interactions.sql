@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS interactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    address TEXT NOT NULL,
+    agent_id TEXT NOT NULL,
+    request_text TEXT NOT NULL,
+    response_text TEXT,
+    http_status INTEGER,
+    latency_ms INTEGER,
+    report_status TEXT NOT NULL,
+    date TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_interactions_address_agent_date
+    ON interactions (address, agent_id, date);
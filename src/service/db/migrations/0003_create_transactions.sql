@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    hash TEXT NOT NULL UNIQUE,
+    address TEXT NOT NULL,
+    nonce INTEGER NOT NULL,
+    to_address TEXT,
+    value TEXT NOT NULL,
+    gas_used INTEGER,
+    effective_gas_price TEXT,
+    status TEXT NOT NULL,
+    block_number INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_address_nonce
+    ON transactions (address, nonce);
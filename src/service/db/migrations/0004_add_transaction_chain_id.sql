@@ -0,0 +1 @@
+ALTER TABLE transactions ADD COLUMN chain_id INTEGER;
@@ -1,109 +1,185 @@
-use chrono::{Duration, Utc};
-use rusqlite::{params, Connection, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 use tokio::sync::{Mutex, OnceCell};
 
+use super::migration;
+use crate::{
+    model::{exception::db_error::DbError, interaction_budget::InteractionBudget},
+    utils::{configuration::Config, quota_window::QuotaWindow},
+};
+
 static DB: OnceCell<Arc<RustQLite>> = OnceCell::const_new();
 
+pub const MEMORY_PATH: &str = ":memory:";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Log {
-    id: i32,
-    address: String,
-    tx_type: String,
-    date: String,
+    pub id: i32,
+    pub address: String,
+    pub tx_type: String,
+    pub date: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Interaction {
+    pub id: i64,
+    pub address: String,
+    pub agent_id: String,
+    pub request_text: String,
+    pub response_text: Option<String>,
+    pub http_status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub report_status: String,
+    pub date: String,
+}
+
+pub struct NewInteraction<'a> {
+    pub address: &'a str,
+    pub agent_id: &'a str,
+    pub request_text: &'a str,
+    pub response_text: Option<&'a str>,
+    pub http_status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub report_status: &'a str,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionRecord {
+    pub id: i64,
+    pub hash: String,
+    pub address: String,
+    pub nonce: u64,
+    pub chain_id: Option<u64>,
+    pub to_address: Option<String>,
+    pub value: String,
+    pub gas_used: Option<u64>,
+    pub effective_gas_price: Option<String>,
+    pub status: String,
+    pub block_number: Option<u64>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewTransaction<'a> {
+    pub hash: &'a str,
+    pub address: &'a str,
+    pub nonce: u64,
+    pub chain_id: u64,
+    pub to_address: Option<&'a str>,
+    pub value: &'a str,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionReceiptUpdate<'a> {
+    pub status: &'a str,
+    pub gas_used: Option<u64>,
+    pub effective_gas_price: Option<&'a str>,
+    pub block_number: Option<u64>,
 }
 
 pub struct RustQLite {
     conn: Mutex<Connection>,
+    quota_window: QuotaWindow,
 }
 
 impl RustQLite {
-    pub async fn init() -> Arc<Self> {
-        DB.get_or_init(|| async {
-            let instance = Arc::new(RustQLite::new());
-            instance.create_table().await;
-            instance
+    pub async fn init() -> Result<Arc<Self>, DbError> {
+        DB.get_or_try_init(|| async {
+            let instance = RustQLite::open(&Config::get().database_path)?
+                .with_quota_window(Config::get().quota_window);
+            Ok(Arc::new(instance))
         })
         .await
-        .clone()
+        .cloned()
     }
 
-    fn new() -> Self {
-        let conn = Connection::open("database.db")
-            .unwrap_or_else(|err| panic!("Error initializing database: {}", err));
-        RustQLite {
-            conn: Mutex::new(conn),
+    pub fn open(path: &str) -> Result<Self, DbError> {
+        let mut conn = if path == MEMORY_PATH {
+            Connection::open_in_memory()
+        } else {
+            Connection::open(path)
         }
+        .map_err(|err| DbError::from(format!("Error initializing database {}: {}", path, err)))?;
+
+        migration::migrate(&mut conn)
+            .map_err(|err| DbError::from(format!("Failed to migrate database: {}", err)))?;
+
+        Ok(RustQLite {
+            conn: Mutex::new(conn),
+            quota_window: QuotaWindow::default(),
+        })
     }
 
-    async fn create_table(&self) {
-        let conn = self.conn.lock().await;
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS log (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                address TEXT NOT NULL,
-                tx_type TEXT NOT NULL,
-                date TEXT NOT NULL
-            )",
-            [],
-        )
-        .unwrap_or_else(|err| panic!("Failed to create table: {}", err));
+    pub fn with_quota_window(mut self, quota_window: QuotaWindow) -> Self {
+        self.quota_window = quota_window;
+        self
     }
 
-    pub async fn insert_log(address: &str, tx_type: &str) {
-        let db = RustQLite::init().await;
+    pub async fn insert_log(&self, address: &str, tx_type: &str) -> Result<(), DbError> {
         let now = Utc::now()
             .naive_utc()
             .format("%Y-%m-%d %H:%M:%S")
             .to_string();
 
-        let conn = db.conn.lock().await;
+        let conn = self.conn.lock().await;
         conn.execute(
             "INSERT INTO log (address, tx_type, date) VALUES (?1, ?2, ?3)",
             params![address, tx_type, now],
         )
-        .unwrap_or_else(|err| panic!("Error inserting log data: {}", err));
+        .map_err(|err| DbError::from(format!("Error inserting log data: {}", err)))?;
+        Ok(())
     }
 
-    pub async fn update_log(id: i32, new_address: &str, new_tx_type: &str) {
-        let db = RustQLite::init().await;
+    pub async fn update_log(
+        &self,
+        id: i32,
+        new_address: &str,
+        new_tx_type: &str,
+    ) -> Result<(), DbError> {
         let now = Utc::now()
             .naive_utc()
             .format("%Y-%m-%d %H:%M:%S")
             .to_string();
 
-        let conn = db.conn.lock().await;
+        let conn = self.conn.lock().await;
         let rows_affected = conn
             .execute(
                 "UPDATE log SET address = ?1, tx_type = ?2, date = ?3 WHERE id = ?4",
                 params![new_address, new_tx_type, now, id],
             )
-            .unwrap_or_else(|err| panic!("Error updating log: {}", err));
+            .map_err(|err| DbError::from(format!("Error updating log: {}", err)))?;
 
         if rows_affected == 0 {
-            panic!("No log found with ID: {}", id);
+            return Err(DbError::from(format!("No log found with ID: {}", id)));
         }
+
+        Ok(())
     }
 
-    pub async fn delete_log(id: i32) {
-        let db = RustQLite::init().await;
-        let conn = db.conn.lock().await;
+    pub async fn delete_log(&self, id: i32) -> Result<(), DbError> {
+        let conn = self.conn.lock().await;
         let rows_affected = conn
             .execute("DELETE FROM log WHERE id = ?1", params![id])
-            .unwrap_or_else(|err| panic!("Error deleting log: {}", err));
+            .map_err(|err| DbError::from(format!("Error deleting log: {}", err)))?;
 
         if rows_affected == 0 {
-            panic!("No log found with ID: {}", id);
+            return Err(DbError::from(format!("No log found with ID: {}", id)));
         }
+
+        Ok(())
     }
 
-    pub async fn get_all_logs() -> Vec<Log> {
-        let db = RustQLite::init().await;
-        let conn = db.conn.lock().await;
+    pub async fn get_all_logs(&self) -> Result<Vec<Log>, DbError> {
+        let conn = self.conn.lock().await;
         let mut stmt = conn
             .prepare("SELECT id, address, tx_type, date FROM log")
-            .unwrap_or_else(|err| panic!("Error preparing statement: {}", err));
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
 
         let rows = stmt
             .query_map([], |row| {
@@ -114,19 +190,16 @@ impl RustQLite {
                     date: row.get(3)?,
                 })
             })
-            .unwrap_or_else(|err| panic!("Error querying logs: {}", err));
+            .map_err(|err| DbError::from(format!("Error querying logs: {}", err)))?;
 
         rows.collect::<Result<Vec<Log>, rusqlite::Error>>()
-            .unwrap_or_else(|err| panic!("Error collecting logs: {}", err))
+            .map_err(|err| DbError::from(format!("Error collecting logs: {}", err)))
     }
 
-    pub async fn get_logs_today(address: &str, tx_type: &str) -> Vec<Log> {
-        let db = RustQLite::init().await;
-        let conn = db.conn.lock().await;
+    pub async fn get_logs_today(&self, address: &str, tx_type: &str) -> Result<Vec<Log>, DbError> {
+        let conn = self.conn.lock().await;
 
-        let now = Utc::now().naive_utc();
-        let today_start = now.date().and_hms_opt(0, 0, 0).unwrap();
-        let today_end = today_start + Duration::days(1);
+        let (today_start, today_end) = self.quota_window.bounds(Utc::now());
 
         let start_str = today_start.format("%Y-%m-%d %H:%M:%S").to_string();
         let end_str = today_end.format("%Y-%m-%d %H:%M:%S").to_string();
@@ -135,9 +208,9 @@ impl RustQLite {
             .prepare(
                 "SELECT id, address, tx_type, date FROM log 
                 WHERE address = ?1 AND tx_type = ?2 
-                AND date BETWEEN ?3 AND ?4",
+                AND date >= ?3 AND date < ?4",
             )
-            .unwrap_or_else(|err| panic!("Error preparing statement: {}", err));
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
 
         let rows = stmt
             .query_map(params![address, tx_type, start_str, end_str], |row| {
@@ -148,9 +221,314 @@ impl RustQLite {
                     date: row.get(3)?,
                 })
             })
-            .unwrap_or_else(|err| panic!("Error querying logs: {}", err));
+            .map_err(|err| DbError::from(format!("Error querying logs: {}", err)))?;
 
         rows.collect::<Result<Vec<Log>, rusqlite::Error>>()
-            .unwrap_or_else(|err| panic!("Error collecting logs: {}", err))
+            .map_err(|err| DbError::from(format!("Error collecting logs: {}", err)))
+    }
+
+    pub async fn insert_interaction(
+        &self,
+        interaction: NewInteraction<'_>,
+    ) -> Result<i64, DbError> {
+        let now = Utc::now()
+            .naive_utc()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO interactions (address, agent_id, request_text, response_text, http_status, latency_ms, report_status, date)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                interaction.address,
+                interaction.agent_id,
+                interaction.request_text,
+                interaction.response_text,
+                interaction.http_status,
+                interaction.latency_ms,
+                interaction.report_status,
+                now
+            ],
+        )
+        .map_err(|err| DbError::from(format!("Error inserting interaction data: {}", err)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub async fn update_interaction_report(
+        &self,
+        id: i64,
+        report_status: &str,
+    ) -> Result<(), DbError> {
+        let conn = self.conn.lock().await;
+        let rows_affected = conn
+            .execute(
+                "UPDATE interactions SET report_status = ?1 WHERE id = ?2",
+                params![report_status, id],
+            )
+            .map_err(|err| DbError::from(format!("Error updating interaction: {}", err)))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::from(format!(
+                "No interaction found with ID: {}",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_interactions(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<Interaction>, DbError> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, address, agent_id, request_text, response_text, http_status, latency_ms, report_status, date
+                FROM interactions WHERE address = ?1
+                ORDER BY id DESC LIMIT ?2",
+            )
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
+
+        let rows = stmt
+            .query_map(params![address, limit], |row| {
+                Ok(Interaction {
+                    id: row.get(0)?,
+                    address: row.get(1)?,
+                    agent_id: row.get(2)?,
+                    request_text: row.get(3)?,
+                    response_text: row.get(4)?,
+                    http_status: row.get(5)?,
+                    latency_ms: row.get(6)?,
+                    report_status: row.get(7)?,
+                    date: row.get(8)?,
+                })
+            })
+            .map_err(|err| DbError::from(format!("Error querying interactions: {}", err)))?;
+
+        rows.collect::<Result<Vec<Interaction>, rusqlite::Error>>()
+            .map_err(|err| DbError::from(format!("Error collecting interactions: {}", err)))
+    }
+
+    pub async fn get_requests_today(
+        &self,
+        address: &str,
+        agent_id: &str,
+    ) -> Result<Vec<String>, DbError> {
+        let conn = self.conn.lock().await;
+
+        let (today_start, today_end) = self.quota_window.bounds(Utc::now());
+
+        let start_str = today_start.format("%Y-%m-%d %H:%M:%S").to_string();
+        let end_str = today_end.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT request_text FROM interactions
+                WHERE address = ?1 AND agent_id = ?2
+                AND date >= ?3 AND date < ?4",
+            )
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
+
+        let rows = stmt
+            .query_map(params![address, agent_id, start_str, end_str], |row| {
+                row.get(0)
+            })
+            .map_err(|err| DbError::from(format!("Error querying interactions: {}", err)))?;
+
+        rows.collect::<Result<Vec<String>, rusqlite::Error>>()
+            .map_err(|err| DbError::from(format!("Error collecting interactions: {}", err)))
+    }
+
+    pub async fn get_interaction_budget(
+        &self,
+        address: &str,
+        global_limit: u32,
+    ) -> Result<InteractionBudget, DbError> {
+        let global_used = self.get_logs_today(address, "interact").await?.len() as u32;
+
+        let conn = self.conn.lock().await;
+        let (today_start, today_end) = self.quota_window.bounds(Utc::now());
+
+        let start_str = today_start.format("%Y-%m-%d %H:%M:%S").to_string();
+        let end_str = today_end.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT agent_id, COUNT(*) FROM interactions
+                WHERE address = ?1 AND report_status = 'reported'
+                AND date >= ?2 AND date < ?3
+                GROUP BY agent_id",
+            )
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
+
+        let rows = stmt
+            .query_map(params![address, start_str, end_str], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+            })
+            .map_err(|err| DbError::from(format!("Error querying interactions: {}", err)))?;
+
+        let agent_used = rows
+            .collect::<Result<HashMap<String, u32>, rusqlite::Error>>()
+            .map_err(|err| DbError::from(format!("Error collecting interactions: {}", err)))?;
+
+        Ok(InteractionBudget {
+            global_used,
+            global_limit,
+            agent_used,
+        })
+    }
+
+    pub async fn insert_transaction(&self, tx: NewTransaction<'_>) -> Result<i64, DbError> {
+        let now = Utc::now()
+            .naive_utc()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO transactions (hash, address, nonce, chain_id, to_address, value, status, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'pending', ?7, ?7)",
+            params![
+                tx.hash,
+                tx.address,
+                tx.nonce,
+                tx.chain_id,
+                tx.to_address,
+                tx.value,
+                now
+            ],
+        )
+        .map_err(|err| DbError::from(format!("Error inserting transaction data: {}", err)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub async fn update_transaction_status(&self, hash: &str, status: &str) -> Result<(), DbError> {
+        self.update_transaction_receipt(
+            hash,
+            TransactionReceiptUpdate {
+                status,
+                gas_used: None,
+                effective_gas_price: None,
+                block_number: None,
+            },
+        )
+        .await
+    }
+
+    pub async fn update_transaction_receipt(
+        &self,
+        hash: &str,
+        receipt: TransactionReceiptUpdate<'_>,
+    ) -> Result<(), DbError> {
+        let now = Utc::now()
+            .naive_utc()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let conn = self.conn.lock().await;
+        let rows_affected = conn
+            .execute(
+                "UPDATE transactions SET status = ?1,
+                gas_used = COALESCE(?2, gas_used),
+                effective_gas_price = COALESCE(?3, effective_gas_price),
+                block_number = COALESCE(?4, block_number),
+                updated_at = ?5
+                WHERE hash = ?6",
+                params![
+                    receipt.status,
+                    receipt.gas_used,
+                    receipt.effective_gas_price,
+                    receipt.block_number,
+                    now,
+                    hash
+                ],
+            )
+            .map_err(|err| DbError::from(format!("Error updating transaction: {}", err)))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::from(format!(
+                "No transaction found with hash {}",
+                hash
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get_transactions(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<TransactionRecord>, DbError> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, hash, address, nonce, chain_id, to_address, value, gas_used, effective_gas_price, status, block_number, created_at, updated_at
+                FROM transactions WHERE address = ?1
+                ORDER BY id DESC LIMIT ?2",
+            )
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
+
+        let rows = stmt
+            .query_map(params![address, limit], |row| {
+                Ok(TransactionRecord {
+                    id: row.get(0)?,
+                    hash: row.get(1)?,
+                    address: row.get(2)?,
+                    nonce: row.get(3)?,
+                    chain_id: row.get(4)?,
+                    to_address: row.get(5)?,
+                    value: row.get(6)?,
+                    gas_used: row.get(7)?,
+                    effective_gas_price: row.get(8)?,
+                    status: row.get(9)?,
+                    block_number: row.get(10)?,
+                    created_at: row.get(11)?,
+                    updated_at: row.get(12)?,
+                })
+            })
+            .map_err(|err| DbError::from(format!("Error querying transactions: {}", err)))?;
+
+        rows.collect::<Result<Vec<TransactionRecord>, rusqlite::Error>>()
+            .map_err(|err| DbError::from(format!("Error collecting transactions: {}", err)))
+    }
+
+    pub async fn get_total_gas_spent(
+        &self,
+        address: &str,
+    ) -> Result<BTreeMap<Option<u64>, u128>, DbError> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(
+                "SELECT chain_id, gas_used, effective_gas_price FROM transactions
+                WHERE address = ?1 AND gas_used IS NOT NULL AND effective_gas_price IS NOT NULL",
+            )
+            .map_err(|err| DbError::from(format!("Error preparing statement: {}", err)))?;
+
+        let rows = stmt
+            .query_map(params![address], |row| {
+                Ok((
+                    row.get::<_, Option<u64>>(0)?,
+                    row.get::<_, u64>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|err| DbError::from(format!("Error querying transactions: {}", err)))?;
+
+        let mut totals: BTreeMap<Option<u64>, u128> = BTreeMap::new();
+        for row in rows {
+            let (chain_id, gas_used, gas_price) = row
+                .map_err(|err| DbError::from(format!("Error collecting transactions: {}", err)))?;
+            let gas_price: u128 = gas_price
+                .parse()
+                .map_err(|_| DbError::from(format!("Invalid gas price '{}'", gas_price)))?;
+            let total = totals.entry(chain_id).or_default();
+            *total = total.saturating_add((gas_used as u128).saturating_mul(gas_price));
+        }
+        Ok(totals)
     }
 }
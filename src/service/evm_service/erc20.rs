@@ -0,0 +1,11 @@
+use ethers::contract::abigen;
+
+abigen!(
+    Erc20,
+    r#"[
+        function balanceOf(address owner) external view returns (uint256)
+        function decimals() external view returns (uint8)
+        function symbol() external view returns (string)
+        function transfer(address to, uint256 amount) external returns (bool)
+    ]"#
+);
@@ -1,71 +1,96 @@
 use crate::{
     model::{
-        exception::operation_error::OperationError, spinner_data::SpinnerData,
+        account_context::AccountContext,
+        exception::operation_error::OperationError,
+        onchain_action::{ActionKind, OnchainAction},
+        spinner_data::SpinnerData,
+        token_balance::TokenBalance,
         user_balance::UserBalance,
     },
+    service::{
+        db::rustqlite::{NewTransaction, RustQLite, TransactionReceiptUpdate},
+        evm_service::{
+            erc20::Erc20,
+            fee_policy::TxFees,
+            nonce_manager::{NonceManager, PendingTx},
+            rpc_pool::RpcPool,
+            simulation::SimulationError,
+        },
+    },
     utils::{
-        network::{Network, RPC},
-        spinner::Spinner,
+        account_vault::AccountVault, configuration::Config, network::Network, spinner::Spinner,
     },
 };
 use ethers::{
     core::k256::ecdsa::SigningKey,
     prelude::*,
     providers::Provider,
-    signers::coins_bip39::English,
     types::transaction::eip2718::TypedTransaction,
-    utils::{format_ether, parse_ether, parse_units},
+    utils::{format_ether, parse_ether},
 };
-use log::info;
+use log::{error, info, warn};
 use rust_decimal::Decimal;
-use std::{str::FromStr, sync::Arc};
+use std::{sync::Arc, time::Duration};
 
 pub struct EvmService {
-    pub acc: Arc<String>,
+    pub acc: Arc<AccountContext>,
     pub address: H160,
-    pub rpc: RPC,
+    pub network: Network,
     pub formatted_address: String,
-    pub client: Arc<SignerMiddleware<Arc<Provider<Http>>, LocalWallet>>,
+    pub client: Arc<SignerMiddleware<Arc<Provider<RpcPool>>, LocalWallet>>,
     pub balance: UserBalance,
+    pub tokens: Vec<TokenBalance>,
+    pub db: Arc<RustQLite>,
 }
 
 impl EvmService {
     pub fn new(
-        acc: &Arc<String>,
+        acc: &Arc<AccountContext>,
         network: &Network,
+        db: &Arc<RustQLite>,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let rpc = network.get_rpc_details();
-        let provider: Arc<Provider<Http>> = Arc::new(Provider::<Http>::try_from(rpc.rpc_url)?);
-
-        let wallet: Wallet<SigningKey> = if acc.split_whitespace().count() > 3 {
-            let wallet: Wallet<SigningKey> = MnemonicBuilder::<English>::default()
-                .phrase(&*acc.to_string())
-                .build()?;
-
-            wallet
+        let proxy = if Config::get().rpc_use_proxy {
+            acc.proxy.to_owned()
         } else {
-            LocalWallet::from_str(&acc)?
+            None
         };
+        let pool = RpcPool::new(network, proxy, Config::get().rpc_health.clone())?;
+        let provider: Arc<Provider<RpcPool>> = Arc::new(Provider::new(pool));
+
+        let wallet: Wallet<SigningKey> =
+            AccountVault::signer(acc).ok_or(format!("No signer for account {}", acc))?;
 
         let formatted_address = format!("0x{:x}", wallet.address());
-        SpinnerData::update(&acc, |data| {
+        SpinnerData::update(acc.index, |data| {
             data.address = formatted_address.to_owned();
         });
         let client = Arc::new(SignerMiddleware::new(
             provider.clone(),
-            wallet.clone().with_chain_id(rpc.chain_id),
+            wallet.clone().with_chain_id(network.chain_id),
         ));
 
         Ok(EvmService {
             acc: acc.to_owned(),
-            rpc,
+            network: network.to_owned(),
             client,
             address: wallet.address(),
             formatted_address,
             balance: SpinnerData::default().balance,
+            tokens: vec![],
+            db: db.to_owned(),
         })
     }
 
+    pub async fn check_rpc_health(&self) {
+        let pool: &RpcPool = self.client.provider().as_ref();
+        pool.check_health().await;
+        info!(
+            "Using RPC {} for network {}",
+            pool.active_url(),
+            self.network.name
+        );
+    }
+
     pub async fn get_balance(&mut self) -> Result<(), OperationError> {
         Spinner::log(&self.acc, "Getting Wallet Balance...", 1000).await;
         match self.client.get_balance(self.address, None).await {
@@ -74,9 +99,11 @@ impl EvmService {
                     gas: format_ether(gas_balance)
                         .parse::<Decimal>()
                         .unwrap_or(Decimal::ZERO),
-                    symbol: self.rpc.symbol.to_string(),
+                    symbol: self.network.symbol.to_owned(),
                 };
-                SpinnerData::update(&self.acc, |data| data.balance = self.balance.to_owned());
+                SpinnerData::update(self.acc.index, |data| {
+                    data.balance = self.balance.to_owned()
+                });
                 Spinner::log(&self.acc, "Successfully Get Wallet Balance...", 1000).await;
             }
             Err(err) => {
@@ -88,10 +115,181 @@ impl EvmService {
         Ok(())
     }
 
-    pub async fn transfer(&self) -> Result<(), OperationError> {
-        Spinner::log(&self.acc, "Trying to Self Transfer...", 1000).await;
+    pub async fn get_token_balances(&mut self) -> Result<(), OperationError> {
+        if self.network.tokens.is_empty() {
+            return Ok(());
+        }
+
+        Spinner::log(&self.acc, "Getting Token Balance...", 1000).await;
+        let mut tokens = vec![];
+        for token in self.network.tokens.iter() {
+            let address: H160 = token
+                .parse()
+                .map_err(|_| OperationError::new(&format!("Invalid token address {}", token)))?;
+            let contract = Erc20::new(address, self.client.clone());
+
+            let symbol = contract.symbol().call().await.map_err(|e| {
+                OperationError::new(&format!("Error getting token {} symbol: {}", token, e))
+            })?;
+            let decimals = contract.decimals().call().await.map_err(|e| {
+                OperationError::new(&format!("Error getting token {} decimals: {}", token, e))
+            })?;
+            let balance = contract
+                .balance_of(self.address)
+                .call()
+                .await
+                .map_err(|e| {
+                    OperationError::new(&format!("Error getting {} balance: {}", symbol, e))
+                })?;
+
+            tokens.push(
+                TokenBalance::new(token, symbol, decimals, balance).map_err(|e| {
+                    OperationError::new(&format!("Error getting token balance: {}", e))
+                })?,
+            );
+        }
+
+        self.tokens = tokens;
+        SpinnerData::update(self.acc.index, |data| data.tokens = self.tokens.to_owned());
+        Spinner::log(&self.acc, "Successfully Get Token Balance...", 1000).await;
+        Ok(())
+    }
+
+    pub async fn transfer_token(
+        &self,
+        token: &TokenBalance,
+        to: String,
+        amount: Decimal,
+    ) -> Result<(), OperationError> {
+        Spinner::log(
+            &self.acc,
+            format!("Trying to Transfer {} {}...", amount, token.symbol).as_str(),
+            1000,
+        )
+        .await;
+        let address: H160 = token.address.parse().map_err(|_| {
+            OperationError::new(&format!("Invalid token address {}", token.address))
+        })?;
+        let recipient: H160 = to
+            .parse()
+            .map_err(|_| OperationError::new(&format!("Invalid recipient address {}", to)))?;
+        let amount = token.raw_amount(amount)?;
+
+        let data = Erc20::new(address, self.client.clone())
+            .transfer(recipient, amount)
+            .calldata()
+            .ok_or("Failed to encode token transfer")?;
         let tx = self
-            .build_tx_body::<Bytes>(None, parse_ether(0)?, self.formatted_address.to_owned())
+            .build_tx_body(Some(data), U256::zero(), token.address.to_owned())
+            .await?;
+
+        match self.execute_tx(tx).await {
+            Ok(_tx_result) => {
+                Spinner::log(&self.acc, "Token Transfer Successful...", 1000).await;
+                Ok(())
+            }
+            Err(err) => Err(OperationError {
+                message: format!("Error During {} Transfer: {}", token.symbol, err),
+            }),
+        }
+    }
+
+    fn resolve_target(&self, target: Option<&str>) -> Result<String, OperationError> {
+        let target = match target {
+            Some(target) => target,
+            None => return Ok(self.formatted_address.to_owned()),
+        };
+
+        match ActionKind::account_ref(target) {
+            Some(number) => AccountVault::address_of(number).ok_or(OperationError::new(&format!(
+                "Account {} not found",
+                number
+            ))),
+            None => Ok(target.trim().to_lowercase()),
+        }
+    }
+
+    pub async fn execute_action(
+        &self,
+        action: &OnchainAction,
+    ) -> Result<Vec<OperationError>, OperationError> {
+        let target = self.resolve_target(action.kind.target())?;
+        match &action.kind {
+            ActionKind::SelfTransfer | ActionKind::WalletTransfer { .. } => {
+                self.transfer(target, action.amount).await.map(|_| vec![])
+            }
+            ActionKind::TokenTransfer { token, .. } => {
+                let tokens: Vec<&TokenBalance> = self
+                    .tokens
+                    .iter()
+                    .filter(|balance| {
+                        token
+                            .as_ref()
+                            .is_none_or(|token| balance.address.eq_ignore_ascii_case(token))
+                    })
+                    .collect();
+                if tokens.is_empty() {
+                    return Err(OperationError::new(&format!(
+                        "No token balance found for action {}",
+                        action.name
+                    )));
+                }
+
+                let mut transferred = 0;
+                let mut failures = vec![];
+                for token in tokens {
+                    let result = if token.balance < action.amount {
+                        Err(OperationError::new(&format!(
+                            "You dont have {} {}",
+                            action.amount, token.symbol
+                        )))
+                    } else {
+                        self.transfer_token(token, target.to_owned(), action.amount)
+                            .await
+                    };
+                    match result {
+                        Ok(()) => transferred += 1,
+                        Err(err) => failures.push(err),
+                    }
+                }
+
+                if transferred == 0 {
+                    return Err(OperationError::new(
+                        &failures
+                            .iter()
+                            .map(|err| err.message.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", "),
+                    ));
+                }
+                Ok(failures)
+            }
+            ActionKind::ContractCall { .. } | ActionKind::RawCalldata { .. } => {
+                let data = action.kind.calldata()?.ok_or("Action has no calldata")?;
+                self.call_contract(&action.name, target, data, action.amount)
+                    .await
+                    .map(|_| vec![])
+            }
+        }
+    }
+
+    pub async fn transfer(&self, to: String, amount: Decimal) -> Result<(), OperationError> {
+        if to == self.formatted_address {
+            Spinner::log(&self.acc, "Trying to Self Transfer...", 1000).await;
+        } else {
+            Spinner::log(
+                &self.acc,
+                format!(
+                    "Trying to Transfer {} {} to {}...",
+                    amount, self.network.symbol, to
+                )
+                .as_str(),
+                1000,
+            )
+            .await;
+        }
+        let tx = self
+            .build_tx_body::<Bytes>(None, parse_ether(amount.to_string())?, to)
             .await?;
 
         match self.execute_tx(tx).await {
@@ -99,28 +297,185 @@ impl EvmService {
                 Spinner::log(&self.acc, "Transfer Successful...", 1000).await;
                 Ok(())
             }
-            Err(err) => {
-                return Err(OperationError {
-                    message: format!("Error During Self Transfer: {}", err),
-                });
+            Err(err) => Err(OperationError {
+                message: format!("Error During Transfer: {}", err),
+            }),
+        }
+    }
+
+    pub async fn call_contract(
+        &self,
+        name: &str,
+        to: String,
+        data: Bytes,
+        amount: Decimal,
+    ) -> Result<(), OperationError> {
+        Spinner::log(
+            &self.acc,
+            format!("Trying to Execute {}...", name).as_str(),
+            1000,
+        )
+        .await;
+        let tx = self
+            .build_tx_body(Some(data), parse_ether(amount.to_string())?, to)
+            .await?;
+
+        match self.execute_tx(tx).await {
+            Ok(_tx_result) => {
+                Spinner::log(&self.acc, format!("{} Successful...", name).as_str(), 1000).await;
+                Ok(())
             }
+            Err(err) => Err(OperationError {
+                message: format!("Error During {}: {}", name, err),
+            }),
         }
     }
 
     pub async fn get_optimal_nonce(&self) -> Result<U256, OperationError> {
-        let latest_nonce = self
-            .client
-            .get_transaction_count(self.address, Some(BlockId::Number(BlockNumber::Latest)))
-            .await
-            .map_err(|e| OperationError::new(&format!("Failed to fetch latest nonce: {}", e)))?;
+        self.replace_stuck_transactions().await?;
 
+        let (latest_nonce, _) = self.sync_nonces().await?;
         let pending_nonce = self
             .client
             .get_transaction_count(self.address, Some(BlockId::Number(BlockNumber::Pending)))
             .await
             .map_err(|e| OperationError::new(&format!("Failed to fetch pending nonce: {}", e)))?;
 
-        Ok(std::cmp::max(latest_nonce, pending_nonce))
+        Ok(NonceManager::with(self.address, |manager| {
+            manager.reserve(std::cmp::max(latest_nonce, pending_nonce))
+        }))
+    }
+
+    async fn sync_nonces(&self) -> Result<(U256, Vec<(U256, TransactionReceipt)>), OperationError> {
+        let latest_nonce = self.get_latest_nonce().await?;
+        let mined = NonceManager::with(self.address, |manager| manager.sync(latest_nonce));
+        let mut receipts = vec![];
+        for pending in mined.iter() {
+            if let Some(receipt) = self.record_mined(pending).await {
+                receipts.push((pending.nonce, receipt));
+            }
+        }
+        Ok((latest_nonce, receipts))
+    }
+
+    async fn get_latest_nonce(&self) -> Result<U256, OperationError> {
+        self.client
+            .get_transaction_count(self.address, Some(BlockId::Number(BlockNumber::Latest)))
+            .await
+            .map_err(|e| OperationError::new(&format!("Failed to fetch latest nonce: {}", e)))
+    }
+
+    pub async fn replace_stuck_transactions(&self) -> Result<(), OperationError> {
+        let timeout = Duration::from_secs(Config::get().stuck_tx_timeout_secs);
+        let stuck = NonceManager::with(self.address, |manager| manager.stuck(timeout));
+
+        for pending in stuck {
+            self.replace_transaction(pending.nonce).await?;
+        }
+        Ok(())
+    }
+
+    async fn replace_transaction(
+        &self,
+        nonce: U256,
+    ) -> Result<Option<PendingTransaction<'_, RpcPool>>, OperationError> {
+        self.sync_nonces().await?;
+        let pending = match NonceManager::with(self.address, |manager| manager.get(nonce)) {
+            Some(pending) => pending,
+            None => return Ok(None),
+        };
+
+        let cancel = pending.replacements >= Config::get().max_tx_replacements;
+        let mut tx = pending.tx.clone();
+        if cancel {
+            tx.set_to(self.address);
+            tx.set_value(U256::zero());
+            tx.set_data(Bytes::new());
+            tx.set_gas(21000);
+        }
+
+        let current_fees = TxFees::of(&pending.tx).ok_or("Pending transaction has no fee set")?;
+        let estimated_fees = self.estimate_fees().await.ok();
+        let fees = Config::get()
+            .fee_policy
+            .replacement_fees(current_fees, estimated_fees)?;
+        fees.apply(&mut tx);
+
+        Spinner::log(
+            &self.acc,
+            format!(
+                "Transaction {:#x} Pending Too Long, {} With Higher Fee...",
+                pending.hash,
+                if cancel { "Cancelling" } else { "Replacing" }
+            )
+            .as_str(),
+            1000,
+        )
+        .await;
+        info!("Replacement Transaction : {:?}", tx);
+
+        match self.client.send_transaction(tx.clone(), None).await {
+            Ok(transaction) => {
+                let tx_hash = transaction.tx_hash();
+                self.record_status(pending.hash, "replaced").await;
+                self.record_transaction(tx_hash, &tx).await;
+                NonceManager::with(self.address, |manager| manager.track(nonce, tx_hash, tx));
+                Ok(Some(transaction))
+            }
+            Err(err) => {
+                if self.sync_nonces().await?.0 > nonce {
+                    return Ok(None);
+                }
+                Err(OperationError::new(&format!(
+                    "Failed to replace transaction with nonce {}: {}",
+                    nonce, err
+                )))
+            }
+        }
+    }
+
+    pub async fn estimate_fees(&self) -> Result<TxFees, OperationError> {
+        let policy = &Config::get().fee_policy;
+
+        if policy.eip1559 {
+            match self
+                .client
+                .fee_history(
+                    policy.fee_history_blocks,
+                    BlockNumber::Latest,
+                    &[policy.reward_percentile],
+                )
+                .await
+            {
+                Ok(history) => match history.base_fee_per_gas.last() {
+                    Some(base_fee) if !base_fee.is_zero() => {
+                        let rewards: Vec<U256> = history
+                            .reward
+                            .iter()
+                            .filter_map(|reward| reward.first().copied())
+                            .collect();
+                        return Ok(policy.eip1559_fees(*base_fee, &rewards)?);
+                    }
+                    _ => warn!("Node returned no base fee, falling back to legacy gas price"),
+                },
+                Err(err) => warn!(
+                    "Fee history not supported ({}), falling back to legacy gas price",
+                    err
+                ),
+            }
+        }
+
+        let node_gas_price = match self.client.get_gas_price().await {
+            Ok(gas_price) => Some(gas_price),
+            Err(err) => {
+                warn!(
+                    "Failed to fetch gas price ({}), using configured price",
+                    err
+                );
+                None
+            }
+        };
+        Ok(policy.legacy_fees(node_gas_price)?)
     }
 
     pub async fn build_tx_body<T: Into<Bytes>>(
@@ -131,9 +486,9 @@ impl EvmService {
     ) -> Result<TypedTransaction, OperationError> {
         let from: H160 = self.formatted_address.parse().unwrap();
         let to: H160 = to.parse().unwrap();
-        let amount_in_wei = parse_ether(amount)? as U256;
+        let amount_in_wei = amount;
 
-        let gas_price_in_gwei: U256 = U256::from(parse_units("5.0", "gwei").unwrap());
+        let fees = self.estimate_fees().await?;
         let optimal_nonce = self.get_optimal_nonce().await?;
         let data: Bytes = match data {
             Some(d) => d.into(),
@@ -145,30 +500,50 @@ impl EvmService {
 
         info!("Building Transaction");
         info!("From      : {}", from);
-        info!("To        : {}", from);
+        info!("To        : {}", to);
         info!("Value     : {}", amount_in_wei);
         info!("Data      : {}", data);
         info!("Nonce     : {}", optimal_nonce);
-        info!("Gas Price : {}", gas_price_in_gwei);
+        info!("Fees      : {:?}", fees);
 
-        let tx: TypedTransaction = if data.is_empty() {
-            TransactionRequest::new()
+        let mut tx: TypedTransaction = match fees {
+            TxFees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => Eip1559TransactionRequest::new()
                 .from(from)
                 .to(to)
                 .value(amount_in_wei)
+                .data(data)
                 .nonce(optimal_nonce)
-                .gas_price(gas_price_in_gwei)
-                .into()
-        } else {
-            TransactionRequest::new()
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                .chain_id(self.network.chain_id)
+                .into(),
+            TxFees::Legacy { gas_price } => TransactionRequest::new()
                 .from(from)
                 .to(to)
                 .value(amount_in_wei)
                 .data(data)
                 .nonce(optimal_nonce)
-                .gas_price(gas_price_in_gwei)
-                .into()
+                .gas_price(gas_price)
+                .chain_id(self.network.chain_id)
+                .into(),
+        };
+
+        let gas_estimate = match self.client.estimate_gas(&tx, None).await {
+            Ok(gas_estimate) => gas_estimate,
+            Err(err) => {
+                NonceManager::with(self.address, |manager| manager.release(optimal_nonce));
+                return Err(OperationError::new(&format!(
+                    "Failed to estimate gas: {}",
+                    SimulationError::from_middleware(&err)
+                )));
+            }
         };
+        let gas_limit = Config::get().fee_policy.gas_limit(gas_estimate);
+        info!("Gas Limit : {} (estimated {})", gas_limit, gas_estimate);
+        tx.set_gas(gas_limit);
 
         info!("Transaction Data : {:?}", tx);
         Ok(tx)
@@ -180,54 +555,242 @@ impl EvmService {
     ) -> Result<Option<TransactionReceipt>, Box<dyn std::error::Error + Send + Sync>> {
         Spinner::log(&self.acc, "Executing Tx ...", 1000).await;
         info!("Transaction : {:?}", tx);
-        let block_id: BlockId = (self.client.get_block_number().await? - 1).into();
-        let transaction = self.client.send_transaction(tx, Some(block_id)).await?;
-        info!("Pending Transaction : {:?}", transaction);
-        let tx_hash = transaction.tx_hash();
-        Spinner::log(
-            &self.acc,
-            format!("Transaction Executed, Hash : {}", tx_hash).as_str(),
-            2000,
-        )
-        .await;
+        let nonce = *tx.nonce().ok_or("Transaction nonce not set")?;
+        if let Err(err) = self.simulate_tx(&tx).await {
+            let require = Config::get().require_simulation;
+            if matches!(err, SimulationError::Reverted(_)) || require {
+                NonceManager::with(self.address, |manager| manager.release(nonce));
+                return Err(format!("Broadcast skipped, {}", err).into());
+            }
+            warn!("{}, broadcasting without simulation", err);
+        }
+        let block_id: BlockId = match self.client.get_block_number().await {
+            Ok(block_number) => (block_number - 1).into(),
+            Err(err) => {
+                NonceManager::with(self.address, |manager| manager.release(nonce));
+                return Err(Box::new(err));
+            }
+        };
+        let mut transaction = match self
+            .client
+            .send_transaction(tx.clone(), Some(block_id))
+            .await
+        {
+            Ok(transaction) => transaction,
+            Err(err) => {
+                NonceManager::with(self.address, |manager| manager.release(nonce));
+                return Err(Box::new(err));
+            }
+        };
+        self.record_transaction(transaction.tx_hash(), &tx).await;
+        NonceManager::with(self.address, |manager| {
+            manager.track(nonce, transaction.tx_hash(), tx)
+        });
+        let timeout = Duration::from_secs(Config::get().stuck_tx_timeout_secs);
 
-        info!("Transaction Hash: {}", format!("{:#x}", tx_hash));
+        loop {
+            info!("Pending Transaction : {:?}", transaction);
+            let tx_hash = transaction.tx_hash();
+            Spinner::log(
+                &self.acc,
+                format!("Transaction Executed, Hash : {}", tx_hash).as_str(),
+                2000,
+            )
+            .await;
 
-        Spinner::log(
-            &self.acc,
-            "Transaction Executed, Waiting For Block Confirmation...",
-            1000,
-        )
-        .await;
-        Ok(match transaction.await {
-            Ok(Some(receipt)) => {
-                Spinner::log(
-                    &self.acc,
-                    format!(
-                        "Transaction Confirmed : {}tx/{:?}",
-                        self.rpc.explorer, receipt.transaction_hash
+            info!("Transaction Hash: {}", format!("{:#x}", tx_hash));
+
+            Spinner::log(
+                &self.acc,
+                "Transaction Executed, Waiting For Block Confirmation...",
+                1000,
+            )
+            .await;
+            match tokio::time::timeout(timeout, transaction).await {
+                Ok(Ok(Some(receipt))) => {
+                    NonceManager::with(self.address, |manager| manager.confirm(nonce));
+                    self.record_receipt(&receipt).await;
+                    Spinner::log(
+                        &self.acc,
+                        format!(
+                            "Transaction Confirmed : {}tx/{:?}",
+                            self.network.explorer, receipt.transaction_hash
+                        )
+                        .as_str(),
+                        5000,
                     )
-                    .as_str(),
-                    5000,
-                )
-                .await;
-                return Ok(Some(receipt));
-            }
-            Ok(None) => {
-                Spinner::log(
-                    &self.acc,
-                    "Transaction was submitted but not found in a block yet.",
-                    5000,
-                )
-                .await;
-                None
+                    .await;
+                    return Ok(Some(receipt));
+                }
+                Ok(Ok(None)) => {
+                    let (latest_nonce, receipts) =
+                        self.sync_nonces().await.map_err(|err| err.message)?;
+                    if latest_nonce > nonce {
+                        let receipt = receipts
+                            .into_iter()
+                            .find(|(mined_nonce, _)| *mined_nonce == nonce)
+                            .map(|(_, receipt)| receipt);
+                        let message = match &receipt {
+                            Some(receipt) => format!(
+                                "Transaction Confirmed : {}tx/{:?}",
+                                self.network.explorer, receipt.transaction_hash
+                            ),
+                            None => format!("Nonce {} Already Used On Chain...", nonce),
+                        };
+                        Spinner::log(&self.acc, &message, 5000).await;
+                        return Ok(receipt);
+                    }
+
+                    NonceManager::with(self.address, |manager| manager.release(nonce));
+                    self.record_status(tx_hash, "dropped").await;
+                    Spinner::log(
+                        &self.acc,
+                        "Transaction was submitted but not found in a block yet.",
+                        5000,
+                    )
+                    .await;
+                    return Ok(None);
+                }
+                Ok(Err(err)) => {
+                    NonceManager::with(self.address, |manager| manager.release(nonce));
+                    self.record_status(tx_hash, "failed").await;
+                    return Err(Box::new(std::io::Error::other(format!(
+                        "Error During Executing Tx : {}",
+                        err
+                    ))));
+                }
+                Err(_) => match self
+                    .replace_transaction(nonce)
+                    .await
+                    .map_err(|err| err.message)?
+                {
+                    Some(replacement) => transaction = replacement,
+                    None => {
+                        Spinner::log(
+                            &self.acc,
+                            "Transaction Confirmed Before Replacement...",
+                            5000,
+                        )
+                        .await;
+                        return Ok(None);
+                    }
+                },
+            }
+        }
+    }
+
+    pub async fn simulate_tx(&self, tx: &TypedTransaction) -> Result<(), SimulationError> {
+        let config = Config::get();
+        if !config.simulate_tx && !config.require_simulation {
+            return Ok(());
+        }
+        Spinner::log(&self.acc, "Simulating Tx ...", 1000).await;
+
+        let fee = TxFees::of(tx).map_or(U256::zero(), |fees| fees.max_fee());
+        let gas = tx.gas().copied().unwrap_or_default();
+        let cost = tx
+            .value()
+            .copied()
+            .unwrap_or_default()
+            .saturating_add(gas.saturating_mul(fee));
+        let balance = self
+            .client
+            .get_balance(self.address, None)
+            .await
+            .map_err(|err| SimulationError::from_middleware(&err))?;
+        if balance < cost {
+            return Err(SimulationError::Reverted(format!(
+                "insufficient balance, need {} {} but have {} {}",
+                format_ether(cost),
+                self.network.symbol,
+                format_ether(balance),
+                self.network.symbol
+            )));
+        }
+
+        match self
+            .client
+            .call(tx, Some(BlockNumber::Pending.into()))
+            .await
+        {
+            Ok(result) => {
+                info!("Simulation Result : {}", result);
+                Ok(())
             }
             Err(err) => {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("Error During Executing Tx : {}", err),
-                )));
+                let err = SimulationError::from_middleware(&err);
+                error!("Simulation Failed : {}", err);
+                Err(err)
             }
-        })
+        }
+    }
+
+    async fn record_transaction(&self, tx_hash: H256, tx: &TypedTransaction) {
+        let hash = format!("{:#x}", tx_hash);
+        let to_address = tx.to_addr().map(|to| format!("0x{:x}", to));
+        let value = tx.value().copied().unwrap_or_default().to_string();
+
+        if let Err(err) = self
+            .db
+            .insert_transaction(NewTransaction {
+                hash: &hash,
+                address: &self.formatted_address,
+                nonce: tx.nonce().map_or(0, |nonce| nonce.as_u64()),
+                chain_id: self.network.chain_id,
+                to_address: to_address.as_deref(),
+                value: &value,
+            })
+            .await
+        {
+            error!("Failed to record transaction {}: {}", hash, err);
+        }
+    }
+
+    async fn record_status(&self, tx_hash: H256, status: &str) {
+        let hash = format!("{:#x}", tx_hash);
+        if let Err(err) = self.db.update_transaction_status(&hash, status).await {
+            error!("Failed to update transaction {}: {}", hash, err);
+        }
+    }
+
+    async fn record_receipt(&self, receipt: &TransactionReceipt) {
+        let hash = format!("{:#x}", receipt.transaction_hash);
+        let status = match receipt.status {
+            Some(status) if status.as_u64() == 1 => "success",
+            Some(_) => "failed",
+            None => "confirmed",
+        };
+        let effective_gas_price = receipt.effective_gas_price.map(|price| price.to_string());
+
+        if let Err(err) = self
+            .db
+            .update_transaction_receipt(
+                &hash,
+                TransactionReceiptUpdate {
+                    status,
+                    gas_used: receipt.gas_used.map(|gas| gas.as_u64()),
+                    effective_gas_price: effective_gas_price.as_deref(),
+                    block_number: receipt.block_number.map(|block| block.as_u64()),
+                },
+            )
+            .await
+        {
+            error!("Failed to update transaction {}: {}", hash, err);
+        }
+    }
+
+    async fn record_mined(&self, pending: &PendingTx) -> Option<TransactionReceipt> {
+        let mut mined = None;
+        for tx_hash in pending.hashes() {
+            match self.client.get_transaction_receipt(tx_hash).await {
+                Ok(Some(receipt)) => {
+                    self.record_receipt(&receipt).await;
+                    mined = Some(receipt);
+                }
+                Ok(None) => self.record_status(tx_hash, "replaced").await,
+                Err(err) => warn!("Failed to fetch receipt {:#x}: {}", tx_hash, err),
+            }
+        }
+        mined
     }
 }
@@ -0,0 +1,196 @@
+use ethers::{
+    types::{transaction::eip2718::TypedTransaction, U256},
+    utils::{format_units, parse_units},
+};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxFees {
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+    Legacy {
+        gas_price: U256,
+    },
+}
+
+impl TxFees {
+    pub fn of(tx: &TypedTransaction) -> Option<TxFees> {
+        match tx {
+            TypedTransaction::Eip1559(tx) => Some(TxFees::Eip1559 {
+                max_fee_per_gas: tx.max_fee_per_gas?,
+                max_priority_fee_per_gas: tx.max_priority_fee_per_gas?,
+            }),
+            _ => tx.gas_price().map(|gas_price| TxFees::Legacy { gas_price }),
+        }
+    }
+
+    pub fn apply(&self, tx: &mut TypedTransaction) {
+        match (self, tx) {
+            (
+                TxFees::Eip1559 {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                },
+                TypedTransaction::Eip1559(tx),
+            ) => {
+                tx.max_fee_per_gas = Some(*max_fee_per_gas);
+                tx.max_priority_fee_per_gas = Some(*max_priority_fee_per_gas);
+            }
+            (fees, tx) => {
+                tx.set_gas_price(fees.max_fee());
+            }
+        }
+    }
+
+    pub fn max_fee(&self) -> U256 {
+        match self {
+            TxFees::Eip1559 {
+                max_fee_per_gas, ..
+            } => *max_fee_per_gas,
+            TxFees::Legacy { gas_price } => *gas_price,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeePolicy {
+    pub eip1559: bool,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub legacy_gas_price: U256,
+    pub fee_history_blocks: u64,
+    pub reward_percentile: f64,
+    pub gas_limit_multiplier: f64,
+    pub replacement_bump_percent: u64,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        FeePolicy {
+            eip1559: true,
+            max_fee_per_gas: Self::parse_gwei("100").unwrap(),
+            max_priority_fee_per_gas: Self::parse_gwei("2").unwrap(),
+            legacy_gas_price: Self::parse_gwei("5").unwrap(),
+            fee_history_blocks: 10,
+            reward_percentile: 50.0,
+            gas_limit_multiplier: 1.2,
+            replacement_bump_percent: 15,
+        }
+    }
+}
+
+impl FeePolicy {
+    pub fn parse_gwei(value: &str) -> Result<U256, String> {
+        parse_units(value.trim(), "gwei")
+            .map(U256::from)
+            .map_err(|_| format!("Invalid gwei amount '{}'", value))
+    }
+
+    pub fn format_gwei(value: U256) -> String {
+        format_units(value, "gwei").unwrap_or(value.to_string())
+    }
+
+    pub fn eip1559_fees(&self, base_fee: U256, rewards: &[U256]) -> Result<TxFees, String> {
+        let mut rewards = rewards.to_vec();
+        rewards.sort();
+        let priority_fee = match rewards.get(rewards.len() / 2) {
+            Some(reward) => (*reward).min(self.max_priority_fee_per_gas),
+            None => self.max_priority_fee_per_gas,
+        };
+
+        if base_fee.saturating_add(priority_fee) > self.max_fee_per_gas {
+            return Err(format!(
+                "Network base fee {} gwei exceeds max fee cap {} gwei",
+                Self::format_gwei(base_fee),
+                Self::format_gwei(self.max_fee_per_gas)
+            ));
+        }
+
+        let max_fee = base_fee
+            .saturating_mul(U256::from(2))
+            .saturating_add(priority_fee)
+            .min(self.max_fee_per_gas);
+
+        Ok(TxFees::Eip1559 {
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: priority_fee,
+        })
+    }
+
+    pub fn legacy_fees(&self, node_gas_price: Option<U256>) -> Result<TxFees, String> {
+        let gas_price = node_gas_price
+            .filter(|price| !price.is_zero())
+            .unwrap_or(self.legacy_gas_price);
+
+        if gas_price > self.max_fee_per_gas {
+            return Err(format!(
+                "Network gas price {} gwei exceeds max fee cap {} gwei",
+                Self::format_gwei(gas_price),
+                Self::format_gwei(self.max_fee_per_gas)
+            ));
+        }
+
+        Ok(TxFees::Legacy { gas_price })
+    }
+
+    pub fn replacement_fees(
+        &self,
+        current: TxFees,
+        estimate: Option<TxFees>,
+    ) -> Result<TxFees, String> {
+        let bump = |fee: U256| {
+            fee.saturating_mul(U256::from(100 + self.replacement_bump_percent.max(10)))
+                / U256::from(100)
+                + 1
+        };
+
+        let fees = match (current, estimate) {
+            (
+                TxFees::Eip1559 {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                },
+                estimate,
+            ) => {
+                let (estimate_max, estimate_priority) = match estimate {
+                    Some(TxFees::Eip1559 {
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                    }) => (max_fee_per_gas, max_priority_fee_per_gas),
+                    _ => (U256::zero(), U256::zero()),
+                };
+                TxFees::Eip1559 {
+                    max_fee_per_gas: bump(max_fee_per_gas).max(estimate_max),
+                    max_priority_fee_per_gas: bump(max_priority_fee_per_gas).max(estimate_priority),
+                }
+            }
+            (TxFees::Legacy { gas_price }, estimate) => {
+                let estimate_price = match estimate {
+                    Some(TxFees::Legacy { gas_price }) => gas_price,
+                    _ => U256::zero(),
+                };
+                TxFees::Legacy {
+                    gas_price: bump(gas_price).max(estimate_price),
+                }
+            }
+        };
+
+        let max_fee = fees.max_fee();
+        if max_fee > self.max_fee_per_gas {
+            return Err(format!(
+                "Replacement fee {} gwei exceeds max fee cap {} gwei",
+                Self::format_gwei(max_fee),
+                Self::format_gwei(self.max_fee_per_gas)
+            ));
+        }
+
+        Ok(fees)
+    }
+
+    pub fn gas_limit(&self, estimate: U256) -> U256 {
+        let multiplier = (self.gas_limit_multiplier.max(1.0) * 100.0) as u64;
+        estimate.saturating_mul(U256::from(multiplier)) / U256::from(100)
+    }
+}
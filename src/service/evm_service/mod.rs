@@ -1 +1,7 @@
+pub mod erc20;
+#[allow(clippy::module_inception)]
 pub mod evm_service;
+pub mod fee_policy;
+pub mod nonce_manager;
+pub mod rpc_pool;
+pub mod simulation;
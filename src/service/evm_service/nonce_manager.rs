@@ -0,0 +1,107 @@
+use dashmap::DashMap;
+use ethers::types::{transaction::eip2718::TypedTransaction, H160, H256, U256};
+use std::{
+    collections::BTreeMap,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+static NONCE_MANAGERS: OnceLock<DashMap<H160, NonceManager>> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct PendingTx {
+    pub nonce: U256,
+    pub hash: H256,
+    pub tx: TypedTransaction,
+    pub replaced: Vec<H256>,
+    pub sent_at: Instant,
+    pub replacements: u32,
+}
+
+impl PendingTx {
+    pub fn hashes(&self) -> Vec<H256> {
+        let mut hashes = self.replaced.clone();
+        hashes.push(self.hash);
+        hashes
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NonceManager {
+    next_nonce: Option<U256>,
+    pending: BTreeMap<U256, PendingTx>,
+}
+
+impl NonceManager {
+    pub fn with<F, R>(address: H160, f: F) -> R
+    where
+        F: FnOnce(&mut NonceManager) -> R,
+    {
+        let managers = NONCE_MANAGERS.get_or_init(DashMap::new);
+        let mut manager = managers.entry(address).or_default();
+        f(&mut manager)
+    }
+
+    pub fn reserve(&mut self, chain_nonce: U256) -> U256 {
+        if self.pending.is_empty() {
+            self.next_nonce = Some(chain_nonce);
+        }
+
+        let nonce = self
+            .next_nonce
+            .map_or(chain_nonce, |next| next.max(chain_nonce));
+        self.next_nonce = Some(nonce + 1);
+        nonce
+    }
+
+    pub fn release(&mut self, nonce: U256) {
+        self.pending.remove(&nonce);
+        if self.next_nonce == Some(nonce + 1) {
+            self.next_nonce = Some(nonce);
+        }
+    }
+
+    pub fn track(&mut self, nonce: U256, hash: H256, tx: TypedTransaction) {
+        let (replaced, replacements) = match self.pending.get(&nonce) {
+            Some(pending) => (pending.hashes(), pending.replacements + 1),
+            None => (vec![], 0),
+        };
+        self.pending.insert(
+            nonce,
+            PendingTx {
+                nonce,
+                hash,
+                tx,
+                replaced,
+                sent_at: Instant::now(),
+                replacements,
+            },
+        );
+    }
+
+    pub fn confirm(&mut self, nonce: U256) {
+        self.pending.remove(&nonce);
+    }
+
+    pub fn sync(&mut self, latest_nonce: U256) -> Vec<PendingTx> {
+        let remaining = self.pending.split_off(&latest_nonce);
+        let mined = std::mem::replace(&mut self.pending, remaining);
+        mined.into_values().collect()
+    }
+
+    pub fn get(&self, nonce: U256) -> Option<PendingTx> {
+        self.pending.get(&nonce).cloned()
+    }
+
+    pub fn stuck(&self, timeout: Duration) -> Vec<PendingTx> {
+        self.pending
+            .values()
+            .filter(|pending| pending.sent_at.elapsed() >= timeout)
+            .cloned()
+            .collect()
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.pending.len()
+    }
+}
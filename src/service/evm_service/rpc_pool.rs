@@ -0,0 +1,385 @@
+use async_trait::async_trait;
+use dashmap::DashMap;
+use ethers::{
+    providers::{Http, HttpClientError, JsonRpcClient, JsonRpcError},
+    types::{H256, U64},
+    utils::{hex, keccak256},
+};
+use ethers_reqwest::{Client, Proxy, Url};
+use log::warn;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use crate::utils::{helper::Helper, network::Network};
+
+const HEALTH_WINDOW: usize = 20;
+
+static RPC_HEALTH: OnceLock<DashMap<String, EndpointHealth>> = OnceLock::new();
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcHealthPolicy {
+    pub max_block_lag: u64,
+    pub max_error_rate: f64,
+    pub max_latency_ms: u64,
+    pub check_interval_secs: u64,
+}
+
+impl Default for RpcHealthPolicy {
+    fn default() -> Self {
+        RpcHealthPolicy {
+            max_block_lag: 5,
+            max_error_rate: 0.5,
+            max_latency_ms: 5000,
+            check_interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EndpointHealth {
+    pub latency_ms: Option<u64>,
+    pub block_number: Option<u64>,
+    pub chain_id: Option<u64>,
+    pub outcomes: VecDeque<bool>,
+    pub last_checked: Option<Instant>,
+}
+
+impl EndpointHealth {
+    pub fn record(&mut self, success: bool, latency: Option<Duration>) {
+        if self.outcomes.len() == HEALTH_WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(success);
+
+        if let Some(latency) = latency {
+            let latency = latency.as_millis() as u64;
+            self.latency_ms = Some(match self.latency_ms {
+                Some(previous) => (previous * 7 + latency * 3) / 10,
+                None => latency,
+            });
+        }
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = self.outcomes.iter().filter(|success| !**success).count();
+        failures as f64 / self.outcomes.len() as f64
+    }
+}
+
+impl RpcHealthPolicy {
+    pub fn is_healthy(&self, health: &EndpointHealth, best_block: Option<u64>) -> bool {
+        let lagging = match (health.block_number, best_block) {
+            (Some(block), Some(best)) => best.saturating_sub(block) > self.max_block_lag,
+            _ => false,
+        };
+        let slow = health
+            .latency_ms
+            .is_some_and(|latency| latency > self.max_latency_ms);
+
+        !lagging && !slow && health.error_rate() <= self.max_error_rate
+    }
+
+    pub fn rank(&self, health: &[EndpointHealth]) -> Vec<usize> {
+        let best_block = health.iter().filter_map(|health| health.block_number).max();
+        let mut order: Vec<usize> = (0..health.len()).collect();
+        order.sort_by(|a, b| {
+            let (a, b) = (&health[*a], &health[*b]);
+            self.is_healthy(b, best_block)
+                .cmp(&self.is_healthy(a, best_block))
+                .then(a.error_rate().total_cmp(&b.error_rate()))
+                .then(
+                    a.latency_ms
+                        .unwrap_or(u64::MAX)
+                        .cmp(&b.latency_ms.unwrap_or(u64::MAX)),
+                )
+        });
+        order
+    }
+}
+
+#[derive(Debug)]
+struct RpcEndpoint {
+    url: String,
+    transport: Http,
+}
+
+#[derive(Debug)]
+pub struct RpcPool {
+    network: String,
+    chain_id: u64,
+    endpoints: Vec<RpcEndpoint>,
+    policy: RpcHealthPolicy,
+}
+
+impl RpcPool {
+    pub fn new(
+        network: &Network,
+        proxy: Option<String>,
+        policy: RpcHealthPolicy,
+    ) -> Result<Self, String> {
+        let client_builder = Client::builder().timeout(Duration::from_secs(30));
+        let client = match proxy {
+            Some(proxy_url) => {
+                if !Helper::is_valid_proxy_format(&proxy_url) {
+                    return Err(format!(
+                        "Invalid proxy format for rpc of network {}",
+                        network.name
+                    ));
+                }
+                let proxy = Proxy::all(&proxy_url).map_err(|_| {
+                    format!("Failed to use proxy for rpc of network {}", network.name)
+                })?;
+                client_builder.proxy(proxy).build()
+            }
+            None => client_builder.build(),
+        }
+        .map_err(|e| format!("Failed to build rpc client: {}", e))?;
+
+        let endpoints = network
+            .rpc_urls
+            .iter()
+            .map(|url| {
+                let parsed =
+                    Url::parse(url).map_err(|e| format!("Invalid rpc url '{}': {}", url, e))?;
+                Ok(RpcEndpoint {
+                    url: url.to_owned(),
+                    transport: Http::new_with_client(parsed, client.clone()),
+                })
+            })
+            .collect::<Result<Vec<RpcEndpoint>, String>>()?;
+
+        Ok(RpcPool {
+            network: network.name.to_owned(),
+            chain_id: network.chain_id,
+            endpoints,
+            policy,
+        })
+    }
+
+    fn storage() -> &'static DashMap<String, EndpointHealth> {
+        RPC_HEALTH.get_or_init(DashMap::new)
+    }
+
+    pub fn health(url: &str) -> EndpointHealth {
+        Self::storage()
+            .get(url)
+            .map(|health| health.clone())
+            .unwrap_or_default()
+    }
+
+    fn record(url: &str, success: bool, latency: Option<Duration>) {
+        Self::storage()
+            .entry(url.to_string())
+            .or_default()
+            .record(success, latency);
+    }
+
+    fn ranked(&self) -> Vec<&RpcEndpoint> {
+        let health: Vec<EndpointHealth> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| Self::health(&endpoint.url))
+            .collect();
+        self.policy
+            .rank(&health)
+            .into_iter()
+            .map(|idx| &self.endpoints[idx])
+            .collect()
+    }
+
+    pub fn active_url(&self) -> &str {
+        self.ranked()
+            .first()
+            .map_or(self.endpoints[0].url.as_str(), |endpoint| {
+                endpoint.url.as_str()
+            })
+    }
+
+    async fn endpoint_chain_id(&self, endpoint: &RpcEndpoint) -> Result<u64, HttpClientError> {
+        if let Some(chain_id) = Self::health(&endpoint.url).chain_id {
+            return Ok(chain_id);
+        }
+
+        let chain_id: U64 = endpoint.transport.request("eth_chainId", ()).await?;
+        Self::storage()
+            .entry(endpoint.url.to_owned())
+            .or_default()
+            .chain_id = Some(chain_id.as_u64());
+        Ok(chain_id.as_u64())
+    }
+
+    async fn is_verified(&self, endpoint: &RpcEndpoint) -> bool {
+        match self.endpoint_chain_id(endpoint).await {
+            Ok(chain_id) if chain_id == self.chain_id => true,
+            Ok(chain_id) => {
+                warn!(
+                    "RPC {} of network {} returned chain id {}, expected {}, skipping broadcast",
+                    endpoint.url, self.network, chain_id, self.chain_id
+                );
+                false
+            }
+            Err(err) => {
+                warn!(
+                    "Unable to verify chain id of {} ({}): {}, skipping broadcast",
+                    self.network, endpoint.url, err
+                );
+                false
+            }
+        }
+    }
+
+    pub async fn verify_chain_id(&self) -> Result<(), String> {
+        let mut verified = false;
+        for endpoint in self.endpoints.iter() {
+            match self.endpoint_chain_id(endpoint).await {
+                Ok(chain_id) if chain_id == self.chain_id => verified = true,
+                Ok(chain_id) => {
+                    return Err(format!(
+                        "RPC {} of network {} returned chain id {}, expected {}",
+                        endpoint.url, self.network, chain_id, self.chain_id
+                    ))
+                }
+                Err(err) => warn!(
+                    "Unable to verify chain id of {} ({}): {}",
+                    self.network, endpoint.url, err
+                ),
+            }
+        }
+
+        if !verified {
+            return Err(format!(
+                "No RPC of network {} answered with chain id {}",
+                self.network, self.chain_id
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_known_transaction(err: &JsonRpcError) -> bool {
+        let message = err.message.to_lowercase();
+        [
+            "already known",
+            "known transaction",
+            "already imported",
+            "nonce too low",
+        ]
+        .iter()
+        .any(|known| message.contains(known))
+    }
+
+    fn raw_tx_hash<T: Serialize>(params: &T) -> Option<H256> {
+        let params = serde_json::to_value(params).ok()?;
+        let raw = params.get(0)?.as_str()?;
+        let raw = hex::decode(raw.trim_start_matches("0x")).ok()?;
+        Some(H256::from(keccak256(raw)))
+    }
+
+    pub async fn check_health(&self) {
+        let interval = Duration::from_secs(self.policy.check_interval_secs);
+        for endpoint in self.endpoints.iter() {
+            let fresh = Self::health(&endpoint.url)
+                .last_checked
+                .is_some_and(|checked| checked.elapsed() < interval);
+            if fresh {
+                continue;
+            }
+
+            let started = Instant::now();
+            let result: Result<U64, HttpClientError> =
+                endpoint.transport.request("eth_blockNumber", ()).await;
+            let mut health = Self::storage().entry(endpoint.url.to_owned()).or_default();
+            health.last_checked = Some(Instant::now());
+            match result {
+                Ok(block_number) => {
+                    health.block_number = Some(block_number.as_u64());
+                    health.record(true, Some(started.elapsed()));
+                }
+                Err(err) => {
+                    warn!(
+                        "RPC {} of network {} failed health check: {}",
+                        endpoint.url, self.network, err
+                    );
+                    health.record(false, None);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for RpcPool {
+    type Error = HttpClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let broadcast = method == "eth_sendRawTransaction";
+        let mut maybe_broadcasted = false;
+        let mut last_error = None;
+        for endpoint in self.ranked() {
+            if broadcast && !self.is_verified(endpoint).await {
+                continue;
+            }
+
+            let started = Instant::now();
+            match endpoint.transport.request(method, &params).await {
+                Ok(result) => {
+                    Self::record(&endpoint.url, true, Some(started.elapsed()));
+                    return Ok(result);
+                }
+                Err(HttpClientError::JsonRpcError(err)) => {
+                    Self::record(&endpoint.url, true, Some(started.elapsed()));
+                    if maybe_broadcasted && Self::is_known_transaction(&err) {
+                        if let Some(tx_hash) = Self::raw_tx_hash(&params) {
+                            warn!(
+                                "RPC {} of network {} already has transaction {:#x} ({}), using previous broadcast",
+                                endpoint.url, self.network, tx_hash, err.message
+                            );
+                            return serde_json::to_value(tx_hash)
+                                .and_then(serde_json::from_value)
+                                .map_err(|err| HttpClientError::SerdeJson {
+                                    err,
+                                    text: format!("{:#x}", tx_hash),
+                                });
+                        }
+                    }
+                    return Err(HttpClientError::JsonRpcError(err));
+                }
+                Err(err) => {
+                    if broadcast
+                        && !matches!(&err, HttpClientError::ReqwestError(err) if err.is_connect())
+                    {
+                        maybe_broadcasted = true;
+                    }
+                    warn!(
+                        "RPC {} of network {} failed on {}: {}, trying next endpoint",
+                        endpoint.url, self.network, method, err
+                    );
+                    Self::record(&endpoint.url, false, None);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(
+            last_error.unwrap_or(HttpClientError::JsonRpcError(JsonRpcError {
+                code: -32000,
+                message: format!(
+                    "No RPC of network {} verified chain id {}, refusing to broadcast",
+                    self.network, self.chain_id
+                ),
+                data: None,
+            })),
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use ethers::{
+    abi::{decode, ParamType, Token},
+    providers::{JsonRpcError, MiddlewareError},
+    types::U256,
+    utils::hex,
+};
+use std::fmt;
+
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+const METHOD_NOT_FOUND: i64 = -32601;
+const EXECUTION_REVERTED: i64 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    Reverted(String),
+    Unavailable(String),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::Reverted(reason) => write!(f, "Transaction would revert: {}", reason),
+            SimulationError::Unavailable(reason) => {
+                write!(f, "Transaction simulation unavailable: {}", reason)
+            }
+        }
+    }
+}
+
+impl SimulationError {
+    pub fn from_middleware<E: MiddlewareError>(err: &E) -> Self {
+        Self::from_rpc(err.as_error_response(), &err.to_string())
+    }
+
+    pub fn from_rpc(response: Option<&JsonRpcError>, fallback: &str) -> Self {
+        let response = match response {
+            Some(response) => response,
+            None => return SimulationError::Unavailable(fallback.to_string()),
+        };
+
+        if response.code == METHOD_NOT_FOUND {
+            return SimulationError::Unavailable(response.message.to_owned());
+        }
+
+        if let Some(data) = response.as_revert_data().filter(|data| !data.is_empty()) {
+            return SimulationError::Reverted(decode_revert(&data));
+        }
+
+        let message = response.message.to_lowercase();
+        if response.code == EXECUTION_REVERTED
+            || message.contains("revert")
+            || message.contains("insufficient funds")
+        {
+            SimulationError::Reverted(response.message.to_owned())
+        } else {
+            SimulationError::Unavailable(response.message.to_owned())
+        }
+    }
+}
+
+pub fn decode_revert(data: &[u8]) -> String {
+    if data.len() < 4 {
+        return "execution reverted".to_string();
+    }
+
+    let (selector, payload) = data.split_at(4);
+    if selector == ERROR_SELECTOR {
+        if let Ok(tokens) = decode(&[ParamType::String], payload) {
+            if let Some(Token::String(reason)) = tokens.into_iter().next() {
+                return reason;
+            }
+        }
+    }
+    if selector == PANIC_SELECTOR {
+        if let Ok(tokens) = decode(&[ParamType::Uint(256)], payload) {
+            if let Some(Token::Uint(code)) = tokens.into_iter().next() {
+                return format!("panic 0x{:02x} ({})", code, panic_reason(code));
+            }
+        }
+    }
+
+    format!("custom error 0x{}", hex::encode(data))
+}
+
+fn panic_reason(code: U256) -> &'static str {
+    if code > U256::from(u8::MAX) {
+        return "unknown panic";
+    }
+    match code.as_u32() {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to zero initialized function",
+        _ => "unknown panic",
+    }
+}
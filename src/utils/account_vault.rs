@@ -0,0 +1,230 @@
+use console::Term;
+use ethers::{
+    core::rand::thread_rng,
+    signers::{LocalWallet, Signer},
+    utils::hex,
+};
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use super::{
+    configuration::Config, helper::Helper, mnemonic_account::MnemonicAccount, wallet_key::WalletKey,
+};
+use crate::model::{
+    account::{Account, AccountEntry},
+    account_context::AccountContext,
+};
+
+const KEYSTORE_PREFIX: &str = "keystore:";
+
+static CONTEXTS: OnceLock<Vec<Arc<AccountContext>>> = OnceLock::new();
+static SIGNERS: OnceLock<Vec<LocalWallet>> = OnceLock::new();
+
+pub struct AccountVault;
+
+impl AccountVault {
+    pub fn init() -> Result<(), String> {
+        let vault_file = Self::vault_path();
+        let mut passphrase: Option<String> = None;
+        let accounts =
+            Self::load(
+                "accounts.json",
+                &vault_file,
+                "proxy_list.json",
+                || match &passphrase {
+                    Some(passphrase) => Ok(passphrase.to_owned()),
+                    None => {
+                        let value = Self::passphrase(false)?;
+                        passphrase = Some(value.to_owned());
+                        Ok(value)
+                    }
+                },
+            )?;
+
+        Account::validate_agents(&accounts, &Config::get().agents)?;
+        Account::validate_networks(&accounts, &Config::get().networks)?;
+
+        let mut contexts = vec![];
+        let mut signers = vec![];
+        for (idx, account) in accounts.into_iter().enumerate() {
+            let signer = WalletKey::to_wallet(&account.key)
+                .map_err(|err| format!("Invalid key for account {}: {}", idx + 1, err))?;
+            let address = format!("0x{:x}", signer.address());
+            contexts.push(Arc::new(AccountContext::new(idx, &account, &address)));
+            signers.push(signer);
+        }
+
+        SIGNERS
+            .set(signers)
+            .map_err(|_| "Accounts already initialized".to_string())?;
+        CONTEXTS
+            .set(contexts)
+            .map_err(|_| "Accounts already initialized".to_string())
+    }
+
+    pub fn contexts() -> &'static [Arc<AccountContext>] {
+        CONTEXTS.get().map_or(&[], |contexts| contexts.as_slice())
+    }
+
+    pub fn signer(acc: &AccountContext) -> Option<LocalWallet> {
+        SIGNERS
+            .get()
+            .and_then(|signers| signers.get(acc.index))
+            .cloned()
+    }
+
+    pub fn address_of(account_number: usize) -> Option<String> {
+        Self::contexts()
+            .get(account_number.checked_sub(1)?)
+            .map(|acc| acc.address.to_owned())
+    }
+
+    pub fn vault_path() -> String {
+        env::var("ACCOUNTS_VAULT").unwrap_or("accounts.vault".to_string())
+    }
+
+    pub fn passphrase(confirm: bool) -> Result<String, String> {
+        if let Ok(passphrase) = env::var("ACCOUNTS_PASSPHRASE") {
+            if !passphrase.is_empty() {
+                return Ok(passphrase);
+            }
+        }
+
+        let passphrase = Self::prompt("Vault Passphrase : ")?;
+        if passphrase.is_empty() {
+            return Err("Vault passphrase cannot be empty".to_string());
+        }
+        if confirm && Self::prompt("Confirm Passphrase : ")? != passphrase {
+            return Err("Vault passphrase does not match".to_string());
+        }
+        Ok(passphrase)
+    }
+
+    fn prompt(label: &str) -> Result<String, String> {
+        print!("{}", label);
+        io::stdout()
+            .flush()
+            .map_err(|err| format!("Failed to prompt passphrase: {}", err))?;
+        let passphrase = if io::stdin().is_terminal() {
+            Term::stderr().read_secure_line()
+        } else {
+            let mut passphrase = String::new();
+            io::stdin().read_line(&mut passphrase).map(|_| passphrase)
+        }
+        .map_err(|err| format!("Failed to read passphrase: {}", err))?;
+        Ok(passphrase.trim_end_matches(['\r', '\n']).to_string())
+    }
+
+    pub fn load<F>(
+        accounts_path: &str,
+        vault_path: &str,
+        proxies_path: &str,
+        mut passphrase: F,
+    ) -> Result<Vec<Account>, String>
+    where
+        F: FnMut() -> Result<String, String>,
+    {
+        let entries = if Path::new(vault_path).exists() {
+            Self::decrypt(vault_path, &passphrase()?)?
+        } else {
+            Account::read_entries(accounts_path)?.ok_or(format!(
+                "Accounts file not detected, please provide {} or {}",
+                accounts_path, vault_path
+            ))?
+        };
+
+        Account::from_entries(entries, Helper::read_data_from_file(proxies_path), |key| {
+            Self::expand_key(key, &mut passphrase)
+        })
+    }
+
+    fn expand_key<F>(key: &str, passphrase: &mut F) -> Result<Vec<String>, String>
+    where
+        F: FnMut() -> Result<String, String>,
+    {
+        match key.trim().strip_prefix(KEYSTORE_PREFIX) {
+            Some(path) => {
+                let password = match env::var("KEYSTORE_PASSWORD") {
+                    Ok(password) if !password.is_empty() => password,
+                    _ => passphrase()?,
+                };
+                Ok(vec![Self::decrypt_keystore(path.trim(), &password)?])
+            }
+            None => MnemonicAccount::expand(key),
+        }
+    }
+
+    pub fn decrypt_keystore(path: &str, password: &str) -> Result<String, String> {
+        let key = eth_keystore::decrypt_key(path, password)
+            .map_err(|err| format!("Failed to decrypt keystore {}: {}", path, err))?;
+        if key.len() != 32 {
+            return Err(format!("Keystore {} does not contain a private key", path));
+        }
+        Ok(format!("0x{}", hex::encode(key)))
+    }
+
+    pub fn decrypt(path: &str, passphrase: &str) -> Result<Vec<AccountEntry>, String> {
+        let data = eth_keystore::decrypt_key(path, passphrase)
+            .map_err(|err| format!("Failed to unlock vault {}: {}", path, err))?;
+        serde_json::from_slice(&data).map_err(|err| format!("Invalid vault {}: {}", path, err))
+    }
+
+    pub fn encrypt(path: &str, accounts: &[AccountEntry], passphrase: &str) -> Result<(), String> {
+        let path = Path::new(path);
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or(format!("Invalid vault path {}", path.display()))?;
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let data = serde_json::to_vec(accounts).map_err(|err| err.to_string())?;
+        let temp_name = format!(".{}.tmp", name);
+        let temp_path = dir.join(&temp_name);
+
+        eth_keystore::encrypt_key(dir, &mut thread_rng(), data, passphrase, Some(&temp_name))
+            .map_err(|err| err.to_string())
+            .and_then(|_| fs::rename(&temp_path, path).map_err(|err| err.to_string()))
+            .map_err(|err| {
+                let _ = fs::remove_file(&temp_path);
+                format!("Failed to write vault {}: {}", path.display(), err)
+            })
+    }
+
+    pub fn import(source: &str, vault_path: &str, passphrase: &str) -> Result<usize, String> {
+        let plaintext =
+            Account::read_entries(source)?.ok_or(format!("Accounts file {} not found", source))?;
+        let mut accounts = if Path::new(vault_path).exists() {
+            Self::decrypt(vault_path, passphrase)?
+        } else {
+            vec![]
+        };
+
+        let mut imported = 0;
+        for (idx, entry) in plaintext.into_iter().enumerate() {
+            let duplicate = accounts
+                .iter()
+                .any(|account| account.key().is_some() && account.key() == entry.key());
+            let key = match entry.key() {
+                Some(key) if !key.is_empty() && !duplicate => key,
+                _ => continue,
+            };
+
+            let invalid =
+                |err: String| format!("Account #{} in {} is invalid: {}", idx + 1, source, err);
+            for key in Self::expand_key(key, &mut || Ok(passphrase.to_string())).map_err(invalid)? {
+                WalletKey::to_wallet(&key).map_err(|err| invalid(err.to_string()))?;
+            }
+            accounts.push(entry);
+            imported += 1;
+        }
+
+        Self::encrypt(vault_path, &accounts, passphrase)?;
+        Ok(imported)
+    }
+}
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Command {
+    Transactions { address: String, limit: u32 },
+    ImportKeys { source: String },
+}
+
+#[derive(Debug, Default)]
+pub struct Cli {
+    pub database_path: Option<String>,
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut cli = Cli::default();
+        let mut args = args.into_iter().skip(1).peekable();
+        let mut limit: Option<u32> = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--db" | "--database" => {
+                    cli.database_path = Some(
+                        args.next()
+                            .ok_or(format!("Missing value for argument {}", arg))?,
+                    );
+                }
+                "--limit" => {
+                    let value = args
+                        .next()
+                        .ok_or(format!("Missing value for argument {}", arg))?;
+                    limit = Some(Self::parse_limit(&value)?);
+                }
+                "transactions" if cli.command.is_none() => {
+                    let address = args
+                        .next()
+                        .ok_or("Missing address for transactions command".to_string())?;
+                    cli.command = Some(Command::Transactions {
+                        address: address.to_lowercase(),
+                        limit: 50,
+                    });
+                }
+                "import-keys" if cli.command.is_none() => {
+                    let source = args
+                        .next_if(|arg| !arg.starts_with("--"))
+                        .unwrap_or("accounts.json".to_string());
+                    cli.command = Some(Command::ImportKeys { source });
+                }
+                _ => match arg.split_once('=') {
+                    Some(("--db", path)) | Some(("--database", path)) => {
+                        cli.database_path = Some(path.to_string());
+                    }
+                    Some(("--limit", value)) => limit = Some(Self::parse_limit(value)?),
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                },
+            }
+        }
+
+        if let Some(value) = limit {
+            match cli.command.as_mut() {
+                Some(Command::Transactions { limit, .. }) => *limit = value,
+                _ => return Err("--limit can only be used with a command".to_string()),
+            }
+        }
+
+        Ok(cli)
+    }
+
+    fn parse_limit(value: &str) -> Result<u32, String> {
+        value
+            .parse()
+            .map_err(|_| format!("Invalid limit '{}'", value))
+    }
+}
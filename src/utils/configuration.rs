@@ -1,4 +1,21 @@
+use super::{
+    cli::{Cli, Command},
+    network::Network,
+    quota_window::QuotaWindow,
+};
+use crate::{
+    model::{
+        account_context::AccountContext,
+        agent::Agent,
+        onchain_action::{ActionKind, ActionPlan, OnchainAction},
+    },
+    service::{
+        api::retry_policy::RetryPolicy,
+        evm_service::{fee_policy::FeePolicy, rpc_pool::RpcHealthPolicy},
+    },
+};
 use dotenvy::dotenv;
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::env;
 use std::sync::OnceLock;
@@ -7,7 +24,24 @@ use std::sync::OnceLock;
 pub struct Config {
     pub real_mode: bool,
     pub use_onchain: bool,
-    pub interaction: i32,
+    pub stream_mode: bool,
+    pub interaction: u32,
+    pub agents: Vec<Agent>,
+    pub retry_policy: RetryPolicy,
+    pub database_path: String,
+    pub quota_window: QuotaWindow,
+    pub quota_reset_offset_secs: i64,
+    pub fee_policy: FeePolicy,
+    pub stuck_tx_timeout_secs: u64,
+    pub max_tx_replacements: u32,
+    pub simulate_tx: bool,
+    pub require_simulation: bool,
+    pub command: Option<Command>,
+    pub networks: Vec<Network>,
+    pub default_network: String,
+    pub rpc_health: RpcHealthPolicy,
+    pub rpc_use_proxy: bool,
+    pub onchain_actions: ActionPlan,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -15,16 +49,79 @@ static CONFIG: OnceLock<Config> = OnceLock::new();
 impl Config {
     pub fn init() -> Result<(), Box<dyn std::error::Error>> {
         dotenv().ok();
+        let cli = Cli::parse(env::args())?;
+        let agents_file = env::var("AGENTS_FILE").unwrap_or("agents.json".to_string());
+        let real_mode = env::var("REAL_MODE")
+            .map(|val| val.parse().unwrap_or(true))
+            .unwrap_or(true);
+        let mut agents = Agent::load(&agents_file, real_mode)?;
+        if let Ok(limits) = env::var("AGENT_DAILY_LIMITS") {
+            Agent::apply_daily_limits(&mut agents, &limits)?;
+        }
+        let networks_file = env::var("NETWORKS_FILE").unwrap_or("networks.json".to_string());
+        let networks = Network::load(&networks_file)?;
+        let default_network = env::var("NETWORK")
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or(networks[0].name.to_owned());
+        if Network::find(&networks, &default_network).is_none() {
+            return Err(format!("Unknown network '{}'", default_network).into());
+        }
         let config = Config {
-            real_mode: env::var("REAL_MODE")
-                .map(|val| val.parse().unwrap_or(true))
-                .unwrap_or(true),
+            real_mode,
             use_onchain: env::var("USE_ONCHAIN")
                 .map(|val| val.parse().unwrap_or(true))
                 .unwrap_or(true),
+            stream_mode: env::var("STREAM_MODE")
+                .map(|val| val.parse().unwrap_or(false))
+                .unwrap_or(false),
             interaction: env::var("DAILY_AGENT_INTERACTION_COUNT")
                 .map(|val| val.parse().unwrap_or(20))
                 .unwrap_or(20),
+            agents,
+            retry_policy: Self::retry_policy_from_env(),
+            database_path: cli
+                .database_path
+                .or(env::var("DATABASE_PATH").ok())
+                .unwrap_or("database.db".to_string()),
+            quota_window: QuotaWindow::new(
+                QuotaWindow::parse_offset(
+                    &env::var("QUOTA_RESET_UTC_OFFSET").unwrap_or("+00:00".to_string()),
+                )?,
+                env::var("QUOTA_RESET_HOUR")
+                    .map(|val| {
+                        val.parse()
+                            .map_err(|_| format!("Invalid QUOTA_RESET_HOUR '{}'", val))
+                    })
+                    .unwrap_or(Ok(0))?,
+            )?,
+            quota_reset_offset_secs: env::var("QUOTA_RESET_OFFSET_SECS")
+                .map(|val| val.parse().unwrap_or(60))
+                .unwrap_or(60),
+            fee_policy: Self::fee_policy_from_env()?,
+            stuck_tx_timeout_secs: env::var("TX_STUCK_TIMEOUT_SECS")
+                .map(|val| val.parse().unwrap_or(180))
+                .unwrap_or(180),
+            max_tx_replacements: env::var("TX_MAX_REPLACEMENTS")
+                .map(|val| val.parse().unwrap_or(3))
+                .unwrap_or(3),
+            simulate_tx: env::var("TX_SIMULATE")
+                .map(|val| val.parse().unwrap_or(true))
+                .unwrap_or(true),
+            require_simulation: env::var("TX_REQUIRE_SIMULATION")
+                .map(|val| val.parse().unwrap_or(false))
+                .unwrap_or(false),
+            command: cli.command,
+            networks,
+            default_network,
+            rpc_health: Self::rpc_health_from_env(),
+            rpc_use_proxy: env::var("RPC_USE_PROXY")
+                .map(|val| val.parse().unwrap_or(false))
+                .unwrap_or(false),
+            onchain_actions: ActionPlan::load(
+                &env::var("ACTIONS_FILE").unwrap_or("actions.json".to_string()),
+                Self::default_actions_from_env()?,
+            )?,
         };
 
         CONFIG
@@ -32,6 +129,124 @@ impl Config {
             .map_err(|_| "Config already initialized".into())
     }
 
+    fn default_actions_from_env() -> Result<ActionPlan, String> {
+        let mut actions = vec![OnchainAction::new(
+            "self-transfer",
+            ActionKind::SelfTransfer,
+            Decimal::ZERO,
+        )];
+
+        let token_transfer = env::var("TOKEN_TRANSFER")
+            .map(|val| val.parse().unwrap_or(false))
+            .unwrap_or(false);
+        if token_transfer {
+            let amount = env::var("TOKEN_TRANSFER_AMOUNT")
+                .map(|val| {
+                    val.parse()
+                        .map_err(|_| format!("Invalid TOKEN_TRANSFER_AMOUNT '{}'", val))
+                })
+                .unwrap_or(Ok(Decimal::ONE))?;
+            actions.push(OnchainAction::new(
+                "token-transfer",
+                ActionKind::TokenTransfer {
+                    token: None,
+                    to: None,
+                },
+                amount,
+            ));
+        }
+
+        Ok(ActionPlan::new(actions))
+    }
+
+    fn retry_policy_from_env() -> RetryPolicy {
+        let default = RetryPolicy::default();
+        RetryPolicy {
+            max_attempts: env::var("API_RETRY_MAX_ATTEMPTS")
+                .map(|val| val.parse().unwrap_or(default.max_attempts))
+                .unwrap_or(default.max_attempts)
+                .max(1),
+            base_delay_ms: env::var("API_RETRY_BASE_DELAY_MS")
+                .map(|val| val.parse().unwrap_or(default.base_delay_ms))
+                .unwrap_or(default.base_delay_ms),
+            max_delay_ms: env::var("API_RETRY_MAX_DELAY_MS")
+                .map(|val| val.parse().unwrap_or(default.max_delay_ms))
+                .unwrap_or(default.max_delay_ms),
+            jitter: env::var("API_RETRY_JITTER")
+                .map(|val| val.parse().unwrap_or(default.jitter))
+                .unwrap_or(default.jitter),
+            retryable_status: env::var("API_RETRY_STATUS")
+                .map(|val| {
+                    val.split(',')
+                        .filter_map(|code| code.trim().parse().ok())
+                        .collect()
+                })
+                .unwrap_or(default.retryable_status),
+            retry_non_idempotent: env::var("API_RETRY_NON_IDEMPOTENT")
+                .map(|val| val.parse().unwrap_or(default.retry_non_idempotent))
+                .unwrap_or(default.retry_non_idempotent),
+        }
+    }
+
+    fn fee_policy_from_env() -> Result<FeePolicy, String> {
+        let default = FeePolicy::default();
+        let gwei = |key: &str, default| {
+            env::var(key)
+                .map(|val| FeePolicy::parse_gwei(&val).map_err(|err| format!("{}: {}", key, err)))
+                .unwrap_or(Ok(default))
+        };
+
+        Ok(FeePolicy {
+            eip1559: env::var("TX_EIP1559")
+                .map(|val| val.parse().unwrap_or(default.eip1559))
+                .unwrap_or(default.eip1559),
+            max_fee_per_gas: gwei("TX_MAX_FEE_GWEI", default.max_fee_per_gas)?,
+            max_priority_fee_per_gas: gwei(
+                "TX_MAX_PRIORITY_FEE_GWEI",
+                default.max_priority_fee_per_gas,
+            )?,
+            legacy_gas_price: gwei("TX_LEGACY_GAS_PRICE_GWEI", default.legacy_gas_price)?,
+            fee_history_blocks: env::var("TX_FEE_HISTORY_BLOCKS")
+                .map(|val| val.parse().unwrap_or(default.fee_history_blocks))
+                .unwrap_or(default.fee_history_blocks)
+                .max(1),
+            reward_percentile: env::var("TX_FEE_REWARD_PERCENTILE")
+                .map(|val| val.parse().unwrap_or(default.reward_percentile))
+                .unwrap_or(default.reward_percentile)
+                .clamp(0.0, 100.0),
+            gas_limit_multiplier: env::var("TX_GAS_LIMIT_MULTIPLIER")
+                .map(|val| val.parse().unwrap_or(default.gas_limit_multiplier))
+                .unwrap_or(default.gas_limit_multiplier),
+            replacement_bump_percent: env::var("TX_REPLACEMENT_BUMP_PERCENT")
+                .map(|val| val.parse().unwrap_or(default.replacement_bump_percent))
+                .unwrap_or(default.replacement_bump_percent),
+        })
+    }
+
+    fn rpc_health_from_env() -> RpcHealthPolicy {
+        let default = RpcHealthPolicy::default();
+        RpcHealthPolicy {
+            max_block_lag: env::var("RPC_MAX_BLOCK_LAG")
+                .map(|val| val.parse().unwrap_or(default.max_block_lag))
+                .unwrap_or(default.max_block_lag),
+            max_error_rate: env::var("RPC_MAX_ERROR_RATE")
+                .map(|val| val.parse().unwrap_or(default.max_error_rate))
+                .unwrap_or(default.max_error_rate)
+                .clamp(0.0, 1.0),
+            max_latency_ms: env::var("RPC_MAX_LATENCY_MS")
+                .map(|val| val.parse().unwrap_or(default.max_latency_ms))
+                .unwrap_or(default.max_latency_ms),
+            check_interval_secs: env::var("RPC_HEALTH_CHECK_SECS")
+                .map(|val| val.parse().unwrap_or(default.check_interval_secs))
+                .unwrap_or(default.check_interval_secs),
+        }
+    }
+
+    pub fn network_for(&self, acc: &AccountContext) -> &Network {
+        let name = acc.network.as_ref().unwrap_or(&self.default_network);
+        Network::find(&self.networks, name).unwrap_or(&self.networks[0])
+    }
+
     pub fn get() -> &'static Config {
         CONFIG.get().expect("Config not initialized")
     }
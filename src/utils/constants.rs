@@ -2,6 +2,8 @@ pub const PROFESSOR_AGENT: &str = "deployment_Hp4Y88pxNQXwLMPxlLICJZzN";
 pub const CRYPTO_BUDDY: &str = "deployment_nC3y3k7zy6gekSZMCSordHu7";
 pub const SHERLOCK: &str = "deployment_SoFftlsf9z4fyA3QCHYkaANq";
 
+pub const AGENT_BASE_URL: &str = "https://{deployment}.stag-vxzy.zettablock.com/main";
+
 pub const PROFFESOR_MESSAGE_LIST: [&str; 50] = [
     "What is Kite AI, and what does it do?",
     "How does Kite AI compare to other AI ecosystems?",
@@ -2,9 +2,11 @@ use log::info;
 
 use crate::{
     model::{
+        account_context::AccountContext,
         api_response::ApiResponse,
         exception::{
-            api_error::ApiError, operation_error::OperationError, setup_error::SetupError,
+            api_error::ApiError, db_error::DbError, operation_error::OperationError,
+            setup_error::SetupError,
         },
     },
     utils::spinner::Spinner,
@@ -15,9 +17,9 @@ use std::error::Error;
 pub struct ExceptionHandler;
 
 impl ExceptionHandler {
-    pub async fn operation_error(acc: &str, error: OperationError) {
+    pub async fn operation_error(acc: &AccountContext, error: OperationError) {
         let error_msg = format!("{}", error);
-        Spinner::log(&acc, &error_msg, 5000).await;
+        Spinner::log(acc, &error_msg, 5000).await;
     }
 
     pub fn create_api_eror(res: ApiResponse) -> ApiError {
@@ -33,22 +35,27 @@ impl ExceptionHandler {
 
         ApiError {
             code: res.status,
-            message: message,
+            message,
         }
     }
-    pub async fn api_error(acc: &str, error: ApiError) {
+    pub async fn api_error(acc: &AccountContext, error: ApiError) {
         let error_msg = format!("{}", error);
-        Spinner::log(&acc, &error_msg, 5000).await;
+        Spinner::log(acc, &error_msg, 5000).await;
     }
 
-    pub async fn setup_error(acc: &str, error: SetupError) {
+    pub async fn db_error(acc: &AccountContext, error: DbError) {
+        let error_msg = format!("{}", error);
+        Spinner::log(acc, &error_msg, 5000).await;
+    }
+
+    pub async fn setup_error(acc: &AccountContext, error: SetupError) {
         let error_msg = format!("{}, exiting in 3 seconds...", error);
-        Spinner::log(&acc, &error_msg, 3000).await;
+        Spinner::log(acc, &error_msg, 3000).await;
         panic!("{}", error.message);
     }
 
-    pub async fn unknown_error(error: Box<dyn Error + Send + Sync>) {
+    pub async fn unknown_error(acc: &AccountContext, error: Box<dyn Error + Send + Sync>) {
         let error_msg = format!("Unknown Error: {}, retrying in 10 seconds...", error);
-        Spinner::log("Unknown", &error_msg, 10000).await;
+        Spinner::log(acc, &error_msg, 10000).await;
     }
 }
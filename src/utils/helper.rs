@@ -1,6 +1,10 @@
 use rand::{rng, seq::IndexedRandom, Rng};
 use serde_json::Value;
-use std::{fs::File, io::Read, time::Duration};
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    time::Duration,
+};
 
 pub struct Helper;
 
@@ -19,6 +23,14 @@ impl Helper {
             .into()
     }
 
+    pub fn read_optional(path: &str) -> Result<Option<String>, String> {
+        match fs::read_to_string(path) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("Failed to read {}: {}", path, err)),
+        }
+    }
+
     pub fn random_user_agent() -> String {
         let ua = [
             "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/125.0.6422.80 Mobile/15E148 Safari/604.1",
@@ -51,14 +63,6 @@ impl Helper {
         format!("{} Hours {} Min {} Sec", hours, minutes, seconds)
     }
 
-    pub fn get_data_index_from_file(acc: &str, path: &str) -> Option<i32> {
-        if let Some(data) = Self::read_data_from_file(path) {
-            data.iter().position(|x| x == acc).map(|index| index as i32)
-        } else {
-            None
-        }
-    }
-
     pub fn is_valid_proxy_format(proxy_url: &str) -> bool {
         let parts: Vec<&str> = proxy_url.split("://").collect();
         if parts.len() != 2 {
@@ -78,7 +82,7 @@ impl Helper {
 
         if let Some(auth) = auth_part {
             let auth_parts: Vec<&str> = auth.split(':').collect();
-            if auth_parts.len() < 1 || auth_parts.len() > 2 {
+            if auth_parts.is_empty() || auth_parts.len() > 2 {
                 return false;
             }
             if auth_parts[0].is_empty() {
@@ -138,6 +142,6 @@ impl Helper {
    ........................................              
 ";
 
-        return logo;
+        logo
     }
 }
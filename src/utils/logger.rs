@@ -4,6 +4,8 @@ use std::{
     io::Write,
 };
 
+use super::redactor::Redactor;
+
 pub struct CustomLogger;
 
 impl Log for CustomLogger {
@@ -37,7 +39,7 @@ impl Log for CustomLogger {
                 timestamp,
                 level,
                 record.target(),
-                record.args()
+                Redactor::redact(&record.args().to_string())
             );
         }
     }
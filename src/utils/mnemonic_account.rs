@@ -0,0 +1,124 @@
+use bip39::{Language, Mnemonic};
+
+const DEFAULT_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
+const HARDENED_OFFSET: u32 = 1 << 31;
+const MAX_RANGE: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MnemonicAccount {
+    pub phrase: String,
+    pub path: Option<String>,
+}
+
+impl MnemonicAccount {
+    pub fn is_mnemonic(entry: &str) -> bool {
+        entry.split_whitespace().count() > 3
+    }
+
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        let (phrase, spec) = Self::split(entry);
+        let phrase = Self::validate_phrase(&phrase)?;
+        let path = match spec {
+            Some(spec) => {
+                let mut paths = Self::expand_path(spec)?;
+                if paths.len() != 1 {
+                    return Err(format!(
+                        "Derivation path '{}' is a range, expected a single account",
+                        spec
+                    ));
+                }
+                paths.pop()
+            }
+            None => None,
+        };
+
+        Ok(MnemonicAccount { phrase, path })
+    }
+
+    pub fn expand(entry: &str) -> Result<Vec<String>, String> {
+        if !Self::is_mnemonic(entry) {
+            return Ok(vec![entry.to_owned()]);
+        }
+
+        let (phrase, spec) = Self::split(entry);
+        let phrase = Self::validate_phrase(&phrase)?;
+        match spec {
+            Some(spec) => Ok(Self::expand_path(spec)?
+                .into_iter()
+                .map(|path| format!("{} {}", phrase, path))
+                .collect()),
+            None => Ok(vec![entry.to_owned()]),
+        }
+    }
+
+    fn split(entry: &str) -> (String, Option<&str>) {
+        let words: Vec<&str> = entry.split_whitespace().collect();
+        match words.split_last() {
+            Some((last, phrase))
+                if last.starts_with("m/") || last.starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                (phrase.join(" "), Some(*last))
+            }
+            _ => (words.join(" "), None),
+        }
+    }
+
+    fn validate_phrase(phrase: &str) -> Result<String, String> {
+        Mnemonic::parse_in(Language::English, phrase)
+            .map(|mnemonic| mnemonic.to_string())
+            .map_err(|err| format!("Invalid mnemonic: {}", err))
+    }
+
+    pub fn expand_path(spec: &str) -> Result<Vec<String>, String> {
+        let path = if spec.starts_with("m/") {
+            spec.to_string()
+        } else {
+            format!("{}{}", DEFAULT_PATH_PREFIX, spec)
+        };
+        let (prefix, last) = path
+            .rsplit_once('/')
+            .ok_or(format!("Invalid derivation path '{}'", spec))?;
+
+        for component in prefix.split('/').skip(1) {
+            Self::parse_component(component)
+                .ok_or(format!("Invalid derivation path '{}'", spec))?;
+        }
+
+        let (start, end) = match last.split_once("..") {
+            Some((start, end)) => (start, end),
+            None => (last, last),
+        };
+        let hardened = end.ends_with(['\'', 'h']);
+        let start = Self::parse_component(start).map(|(index, _)| index);
+        let end = Self::parse_component(end).map(|(index, _)| index);
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) if start <= end => (start, end),
+            _ => return Err(format!("Invalid derivation path '{}'", spec)),
+        };
+        if end - start >= MAX_RANGE {
+            return Err(format!(
+                "Derivation range '{}' is larger than {} accounts",
+                spec, MAX_RANGE
+            ));
+        }
+
+        Ok((start..=end)
+            .map(|index| format!("{}/{}{}", prefix, index, if hardened { "'" } else { "" }))
+            .collect())
+    }
+
+    fn parse_component(component: &str) -> Option<(u32, bool)> {
+        let (index, hardened) = match component.strip_suffix(['\'', 'h']) {
+            Some(index) => (index, true),
+            None => (component, false),
+        };
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        index
+            .parse::<u32>()
+            .ok()
+            .filter(|index| *index < HARDENED_OFFSET)
+            .map(|index| (index, hardened))
+    }
+}
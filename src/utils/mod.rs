@@ -1,7 +1,13 @@
+pub mod account_vault;
+pub mod cli;
 pub mod configuration;
 pub mod constants;
 pub mod exception_handler;
 pub mod helper;
 pub mod logger;
+pub mod mnemonic_account;
 pub mod network;
+pub mod quota_window;
+pub mod redactor;
 pub mod spinner;
+pub mod wallet_key;
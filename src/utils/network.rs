@@ -1,24 +1,145 @@
-pub enum Network {
-    KITEAI,
-}
+use ethers::types::H160;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, str::FromStr};
+
+use super::helper::Helper;
 
-#[derive(Clone)]
-pub struct RPC {
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Network {
+    pub name: String,
     pub chain_id: u64,
-    pub rpc_url: &'static str,
-    pub explorer: &'static str,
-    pub symbol: &'static str,
+    pub rpc_urls: Vec<String>,
+    pub explorer: String,
+    pub symbol: String,
+    pub tokens: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NetworkDefinition {
+    name: Option<String>,
+    chain_id: Option<u64>,
+    rpc_urls: Option<Vec<String>>,
+    explorer: Option<String>,
+    symbol: Option<String>,
+    tokens: Option<Vec<String>>,
 }
 
 impl Network {
-    pub fn get_rpc_details(&self) -> RPC {
-        match self {
-            Network::KITEAI => RPC {
-                chain_id: 2368,
-                rpc_url: "https://rpc-testnet.gokite.ai",
-                explorer: "https://testnet.kitescan.ai/",
-                symbol: "KITE",
-            },
+    pub fn kite_testnet() -> Self {
+        Network {
+            name: "kite-testnet".to_string(),
+            chain_id: 2368,
+            rpc_urls: vec!["https://rpc-testnet.gokite.ai".to_string()],
+            explorer: "https://testnet.kitescan.ai/".to_string(),
+            symbol: "KITE".to_string(),
+            tokens: vec![],
+        }
+    }
+
+    pub fn defaults() -> Vec<Network> {
+        vec![Self::kite_testnet()]
+    }
+
+    pub fn find<'a>(networks: &'a [Network], name: &str) -> Option<&'a Network> {
+        networks
+            .iter()
+            .find(|network| network.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn find_by_chain_id(networks: &[Network], chain_id: u64) -> Option<&Network> {
+        networks.iter().find(|network| network.chain_id == chain_id)
+    }
+
+    pub fn load(path: &str) -> Result<Vec<Network>, String> {
+        match Helper::read_optional(path)? {
+            Some(data) => Self::parse(&data).map_err(|err| format!("{}: {}", path, err)),
+            None => Ok(Self::defaults()),
         }
     }
+
+    pub fn parse(data: &str) -> Result<Vec<Network>, String> {
+        let definitions: Vec<NetworkDefinition> =
+            serde_json::from_str(data).map_err(|err| format!("Invalid networks file: {}", err))?;
+
+        let mut networks = vec![];
+        for (idx, definition) in definitions.into_iter().enumerate() {
+            let position = idx + 1;
+            let name = definition
+                .name
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .ok_or(format!("Network #{} is missing name", position))?;
+            let chain_id = definition
+                .chain_id
+                .filter(|chain_id| *chain_id > 0)
+                .ok_or(format!(
+                    "Network #{} ({}) is missing chain_id",
+                    position, name
+                ))?;
+
+            let rpc_urls: Vec<String> = definition
+                .rpc_urls
+                .unwrap_or_default()
+                .into_iter()
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+                .collect();
+            if rpc_urls.is_empty() {
+                return Err(format!("Network #{} ({}) has no rpc_urls", position, name));
+            }
+            if let Some(url) = rpc_urls
+                .iter()
+                .find(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+            {
+                return Err(format!(
+                    "Network #{} ({}) has invalid rpc url '{}', expected http(s) url",
+                    position, name, url
+                ));
+            }
+
+            let mut explorer = definition.explorer.unwrap_or_default().trim().to_string();
+            if !explorer.is_empty() && !explorer.ends_with('/') {
+                explorer.push('/');
+            }
+
+            let tokens: Vec<String> = definition
+                .tokens
+                .unwrap_or_default()
+                .into_iter()
+                .map(|token| token.trim().to_lowercase())
+                .collect();
+            if let Some(token) = tokens.iter().find(|token| H160::from_str(token).is_err()) {
+                return Err(format!(
+                    "Network #{} ({}) has invalid token address '{}'",
+                    position, name, token
+                ));
+            }
+
+            networks.push(Network {
+                name,
+                chain_id,
+                rpc_urls,
+                explorer,
+                symbol: definition
+                    .symbol
+                    .map(|symbol| symbol.trim().to_string())
+                    .filter(|symbol| !symbol.is_empty())
+                    .unwrap_or("ETH".to_string()),
+                tokens,
+            });
+        }
+
+        let mut seen = HashSet::new();
+        for network in networks.iter() {
+            if !seen.insert(network.name.to_lowercase()) {
+                return Err(format!("Duplicate network name '{}'", network.name));
+            }
+        }
+
+        if networks.is_empty() {
+            return Err("No network found".to_string());
+        }
+
+        Ok(networks)
+    }
 }
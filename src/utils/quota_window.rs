@@ -0,0 +1,90 @@
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct QuotaWindow {
+    pub offset_minutes: i32,
+    pub reset_hour: u32,
+}
+
+impl QuotaWindow {
+    pub fn new(offset_minutes: i32, reset_hour: u32) -> Result<Self, String> {
+        if offset_minutes.abs() > 14 * 60 {
+            return Err(format!(
+                "Invalid quota timezone offset {} minutes, expected between -14:00 and +14:00",
+                offset_minutes
+            ));
+        }
+        if reset_hour > 23 {
+            return Err(format!(
+                "Invalid quota reset hour {}, expected between 0 and 23",
+                reset_hour
+            ));
+        }
+
+        Ok(QuotaWindow {
+            offset_minutes,
+            reset_hour,
+        })
+    }
+
+    pub fn parse_offset(value: &str) -> Result<i32, String> {
+        let trimmed = value.trim();
+        let raw = trimmed
+            .strip_prefix("UTC")
+            .or(trimmed.strip_prefix("GMT"))
+            .unwrap_or(trimmed)
+            .trim();
+        if raw.is_empty() || raw == "Z" {
+            return Ok(0);
+        }
+
+        let (sign, digits) = match raw.chars().next() {
+            Some('-') => (-1, &raw[1..]),
+            Some('+') => (1, &raw[1..]),
+            _ => (1, raw),
+        };
+
+        let (hours, minutes) = match digits.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if digits.len() == 4 && digits.is_ascii() => digits.split_at(2),
+            None => (digits, "0"),
+        };
+
+        let is_numeric = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        if !is_numeric(hours) || !is_numeric(minutes) {
+            return Err(format!(
+                "Invalid quota timezone offset '{}', expected format like +07:00",
+                value
+            ));
+        }
+
+        match (hours.parse::<i32>(), minutes.parse::<i32>()) {
+            (Ok(hours), Ok(minutes)) if (0..60).contains(&minutes) => {
+                Ok(sign * (hours * 60 + minutes))
+            }
+            _ => Err(format!(
+                "Invalid quota timezone offset '{}', expected format like +07:00",
+                value
+            )),
+        }
+    }
+
+    pub fn bounds(&self, now: DateTime<Utc>) -> (NaiveDateTime, NaiveDateTime) {
+        let offset = Duration::minutes(self.offset_minutes as i64);
+        let local_now = now.naive_utc() + offset;
+        let reset_time = NaiveTime::from_hms_opt(self.reset_hour, 0, 0).unwrap_or(NaiveTime::MIN);
+
+        let mut local_start = local_now.date().and_time(reset_time);
+        if local_now < local_start {
+            local_start -= Duration::days(1);
+        }
+
+        let start = local_start - offset;
+        (start, start + Duration::days(1))
+    }
+
+    pub fn next_reset(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.bounds(now).1.and_utc()
+    }
+}
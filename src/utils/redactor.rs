@@ -0,0 +1,85 @@
+use bip39::Language;
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+const REDACTED: &str = "[REDACTED]";
+const MIN_MNEMONIC_WORDS: usize = 12;
+
+struct Patterns {
+    proxy_credentials: Regex,
+    bearer_token: Regex,
+    private_key: Regex,
+    word_run: Regex,
+}
+
+static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+
+pub struct Redactor;
+
+impl Redactor {
+    fn patterns() -> &'static Patterns {
+        PATTERNS.get_or_init(|| Patterns {
+            proxy_credentials: Regex::new(r"(?i)\b([a-z][a-z0-9+.-]*://)[^\s:@/]+:[^\s/]+@")
+                .unwrap(),
+            bearer_token: Regex::new(r"(?i)\b(bearer\s+)[a-z0-9\-._~+/]+=*").unwrap(),
+            private_key: Regex::new(
+                r"(Transaction Hash: |https?://\S+/tx/)?\b(?:0x)?[0-9a-fA-F]{64}\b",
+            )
+            .unwrap(),
+            word_run: Regex::new(r"(?i)\b[a-z]{3,8}(?:\s+[a-z]{3,8}){11,}\b").unwrap(),
+        })
+    }
+
+    pub fn redact(input: &str) -> String {
+        let patterns = Self::patterns();
+
+        let output = patterns
+            .proxy_credentials
+            .replace_all(input, format!("${{1}}{}@", REDACTED));
+        let output = patterns
+            .bearer_token
+            .replace_all(&output, format!("${{1}}{}", REDACTED));
+        let output = patterns
+            .private_key
+            .replace_all(&output, |caps: &Captures| match caps.get(1) {
+                Some(_) => caps[0].to_string(),
+                None => REDACTED.to_string(),
+            });
+        let output = patterns
+            .word_run
+            .replace_all(&output, |caps: &Captures| Self::redact_mnemonic(&caps[0]));
+
+        output.into_owned()
+    }
+
+    fn redact_mnemonic(text: &str) -> String {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut output: Vec<&str> = vec![];
+        let mut run: Vec<&str> = vec![];
+        let mut redacted = false;
+
+        for word in words.into_iter().chain([""]) {
+            if !word.is_empty() && Language::English.find_word(&word.to_lowercase()).is_some() {
+                run.push(word);
+                continue;
+            }
+
+            if run.len() >= MIN_MNEMONIC_WORDS {
+                output.push(REDACTED);
+                redacted = true;
+            } else {
+                output.append(&mut run);
+            }
+            run.clear();
+            if !word.is_empty() {
+                output.push(word);
+            }
+        }
+
+        if redacted {
+            output.join(" ")
+        } else {
+            text.to_string()
+        }
+    }
+}
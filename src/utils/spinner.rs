@@ -7,14 +7,14 @@ use tokio::sync::OnceCell;
 use tokio::time::sleep;
 
 use crate::{
-    model::spinner_data::SpinnerData, service::db::rustqlite::RustQLite,
+    model::{account_context::AccountContext, spinner_data::SpinnerData},
     utils::configuration::Config,
 };
 
 use super::helper::Helper;
 
 static MULTI_PROGRESS: OnceCell<Arc<MultiProgress>> = OnceCell::const_new();
-static SPINNERS: OnceCell<Arc<DashMap<String, ProgressBar>>> = OnceCell::const_new();
+static SPINNERS: OnceCell<Arc<DashMap<usize, ProgressBar>>> = OnceCell::const_new();
 
 #[derive(Clone)]
 pub struct Spinner;
@@ -30,15 +30,15 @@ impl Spinner {
         SpinnerData::init().await;
     }
 
-    pub async fn log(acc: &str, msg: &str, delay: u64) {
-        let acc_idx = Helper::get_data_index_from_file(acc, "accounts.json");
+    pub async fn log(acc: &AccountContext, msg: &str, delay: u64) {
+        let interaction_limit = acc.interaction_limit(Config::get().interaction);
 
-        info!("Account {} : {}", acc_idx.unwrap_or(0) + 1, msg);
+        info!("Account {} : {}", acc, msg);
         let multi_progress = MULTI_PROGRESS.get().expect("MultiProgress not initialized");
         let spinners = SPINNERS.get().expect("Spinners not initialized");
-        let spinner_data = SpinnerData::get_or_create(acc);
+        let spinner_data = SpinnerData::get_or_create(acc.index);
 
-        let pb = spinners.entry(acc.to_string()).or_insert_with(|| {
+        let pb = spinners.entry(acc.index).or_insert_with(|| {
             let progress_bar = multi_progress.add(ProgressBar::new_spinner());
             progress_bar.set_style(
                 ProgressStyle::default_spinner()
@@ -46,38 +46,88 @@ impl Spinner {
                     .unwrap()
                     .progress_chars("##-"),
             );
-            progress_bar.set_prefix(acc.to_string());
             progress_bar.enable_steady_tick(Duration::from_millis(100));
             progress_bar
         });
 
         let mut remaining_duration = Duration::from_millis(delay);
+        let (interaction_today, agent_budget) = match &spinner_data.budget {
+            Some(budget) => (
+                budget.global_used.to_string(),
+                Config::get()
+                    .agents
+                    .iter()
+                    .filter(|agent| agent.enabled && acc.allows_agent(agent))
+                    .map(|agent| {
+                        let limit = agent
+                            .daily_limit
+                            .map_or("-".to_string(), |limit| limit.to_string());
+                        format!(
+                            "{:<20}: {}/{} ({} left)\n",
+                            agent.name,
+                            budget.used_by(agent),
+                            limit,
+                            budget.remaining_for(agent)
+                        )
+                    })
+                    .collect::<String>(),
+            ),
+            None => ("-".to_string(), String::new()),
+        };
+
+        let mut onchain_actions = String::new();
+        if acc.use_onchain(Config::get().use_onchain) {
+            let actions = Config::get()
+                .onchain_actions
+                .for_account(acc.number(), &acc.address);
+            for action in actions.iter().filter(|action| action.enabled) {
+                let done = spinner_data
+                    .actions_today
+                    .get(&action.log_type())
+                    .map_or("-".to_string(), |done| done.to_string());
+                let limit = action
+                    .daily_count
+                    .map_or("-".to_string(), |limit| limit.to_string());
+                onchain_actions.push_str(&format!("{:<20}: {}/{}\n", action.name, done, limit));
+            }
+        }
 
-        while remaining_duration > Duration::from_millis(0) {
+        let token_balance: String = spinner_data
+            .tokens
+            .iter()
+            .map(|token| {
+                format!(
+                    "{:<20}: {:?} {}\n",
+                    token.symbol, token.balance, token.symbol
+                )
+            })
+            .collect();
+
+        loop {
             let formatted_message = format!(
                 r#"
 ================= Account {} ===============
 Address             : {}
 Balance             : {:?} {}
-Interaction (Today) : {:?} ({:?}/{:?})
-
+{}Interaction (Today) : {:?} ({}/{:?})
+{}{}
 Status : {}
 Delay : {}
 ==========================================
 "#,
-                acc_idx.unwrap_or(0) + 1,
-                spinner_data.address,
+                acc,
+                acc.address,
                 spinner_data.balance.gas,
                 spinner_data.balance.symbol.as_str(),
+                token_balance,
                 spinner_data
                     .stats
-                    .get("total_interactions")
-                    .and_then(|bal| bal.as_u64())
-                    .map_or_else(|| 0, |b| b),
-                RustQLite::get_logs_today(&spinner_data.address, "interact")
-                    .await
-                    .len(),
-                Config::get().interaction,
+                    .as_ref()
+                    .map_or(0, |stats| stats.total_interactions),
+                interaction_today,
+                interaction_limit,
+                agent_budget,
+                onchain_actions,
                 msg,
                 Helper::ms_to_time(remaining_duration.as_millis() as u64)
             );
@@ -85,6 +135,10 @@ Delay : {}
             pb.set_message(formatted_message.clone());
             pb.tick();
 
+            if remaining_duration.is_zero() {
+                break;
+            }
+
             let refresh_duration = Duration::from_millis(100);
             remaining_duration = remaining_duration.saturating_sub(refresh_duration);
 
@@ -0,0 +1,21 @@
+use ethers::signers::{coins_bip39::English, LocalWallet, MnemonicBuilder};
+use std::str::FromStr;
+
+use super::mnemonic_account::MnemonicAccount;
+
+pub struct WalletKey;
+
+impl WalletKey {
+    pub fn to_wallet(key: &str) -> Result<LocalWallet, Box<dyn std::error::Error + Send + Sync>> {
+        if MnemonicAccount::is_mnemonic(key) {
+            let account = MnemonicAccount::parse(key)?;
+            let mut builder = MnemonicBuilder::<English>::default().phrase(account.phrase.as_str());
+            if let Some(path) = &account.path {
+                builder = builder.derivation_path(path)?;
+            }
+            Ok(builder.build()?)
+        } else {
+            Ok(LocalWallet::from_str(key)?)
+        }
+    }
+}
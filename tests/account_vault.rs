@@ -0,0 +1,138 @@
+use ethers::{
+    core::rand::thread_rng,
+    signers::{LocalWallet, Signer},
+};
+use kite_ai_bot::{model::account::AccountEntry, utils::account_vault::AccountVault};
+use std::{fs, path::PathBuf};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("kite-ai-bot-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn vault_round_trip_requires_passphrase() {
+    let dir = temp_dir("vault");
+    let vault = dir.join("accounts.vault");
+    let vault = vault.to_str().unwrap();
+    let keys = vec![
+        "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".to_string(),
+        "test test test test test test test test test test test junk".to_string(),
+    ];
+    let accounts: Vec<AccountEntry> = keys.iter().cloned().map(AccountEntry::Key).collect();
+
+    AccountVault::encrypt(vault, &accounts, "correct horse").unwrap();
+
+    let data = fs::read_to_string(vault).unwrap();
+    assert!(!data.contains("4c0883a6"));
+    assert!(!data.contains("junk"));
+    assert_eq!(
+        AccountVault::decrypt(vault, "correct horse").unwrap(),
+        accounts
+    );
+    assert!(AccountVault::decrypt(vault, "wrong").is_err());
+
+    let proxies = dir.join("proxy_list.json");
+    fs::write(&proxies, "[]").unwrap();
+    let loaded = AccountVault::load("missing.json", vault, proxies.to_str().unwrap(), || {
+        Ok("correct horse".to_string())
+    })
+    .unwrap();
+    let loaded: Vec<String> = loaded.into_iter().map(|account| account.key).collect();
+    assert_eq!(loaded, keys);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn keystore_entries_are_decrypted_in_memory() {
+    let dir = temp_dir("keystore");
+    let (wallet, id) = LocalWallet::new_keystore(&dir, &mut thread_rng(), "secret", None).unwrap();
+    let keystore = dir.join(id);
+    let accounts = dir.join("accounts.json");
+    fs::write(
+        &accounts,
+        format!(r#"["keystore:{}"]"#, keystore.to_str().unwrap()),
+    )
+    .unwrap();
+
+    let loaded = AccountVault::load(
+        accounts.to_str().unwrap(),
+        dir.join("missing.vault").to_str().unwrap(),
+        dir.join("missing.json").to_str().unwrap(),
+        || Ok("secret".to_string()),
+    );
+    assert!(loaded.is_err());
+
+    fs::write(
+        &accounts,
+        format!(
+            r#"[{{ "label": "main", "key": "keystore:{}" }}]"#,
+            keystore.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+    let loaded = AccountVault::load(
+        accounts.to_str().unwrap(),
+        dir.join("missing.vault").to_str().unwrap(),
+        dir.join("missing.json").to_str().unwrap(),
+        || Ok("secret".to_string()),
+    )
+    .unwrap();
+    assert_eq!(loaded[0].label.as_deref(), Some("main"));
+    let decrypted: LocalWallet = loaded[0].key.parse().unwrap();
+    assert_eq!(decrypted.address(), wallet.address());
+
+    assert!(AccountVault::decrypt_keystore(keystore.to_str().unwrap(), "wrong").is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn import_merges_plaintext_accounts_into_vault() {
+    let dir = temp_dir("import");
+    let vault = dir.join("accounts.vault");
+    let vault = vault.to_str().unwrap();
+    let source = dir.join("accounts.json");
+    let source = source.to_str().unwrap();
+    let key = |byte: u8| format!("0x{}", format!("{:02x}", byte).repeat(32));
+
+    fs::write(
+        source,
+        format!(r#"["{}", "{}", "{}"]"#, key(1), key(2), key(1)),
+    )
+    .unwrap();
+    assert_eq!(AccountVault::import(source, vault, "pass").unwrap(), 2);
+
+    fs::write(
+        source,
+        format!(
+            r#"["{}", {{ "label": "third", "key": "{}" }}]"#,
+            key(2),
+            key(3)
+        ),
+    )
+    .unwrap();
+    assert_eq!(AccountVault::import(source, vault, "pass").unwrap(), 1);
+    let keys: Vec<String> = AccountVault::decrypt(vault, "pass")
+        .unwrap()
+        .iter()
+        .map(|entry| entry.key().unwrap().to_string())
+        .collect();
+    assert_eq!(keys, vec![key(1), key(2), key(3)]);
+    assert!(AccountVault::import(source, vault, "other").is_err());
+
+    for invalid in [
+        r#"["key-4"]"#,
+        r#"["test test test test test test test test test test test test"]"#,
+        r#"["keystore:missing.json"]"#,
+    ] {
+        fs::write(source, invalid).unwrap();
+        let err = AccountVault::import(source, vault, "pass").unwrap_err();
+        assert!(err.contains("Account #1"), "{}", err);
+        assert!(!err.contains("key-4"), "{}", err);
+    }
+    assert_eq!(AccountVault::decrypt(vault, "pass").unwrap().len(), 3);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,64 @@
+use kite_ai_bot::model::agent::Agent;
+
+#[test]
+fn agents_are_parsed_with_default_fallbacks() {
+    let agents = Agent::parse(
+        r#"[
+            { "deployment_id": "deployment_Hp4Y88pxNQXwLMPxlLICJZzN" },
+            {
+                "name": " Weather ",
+                "deployment_id": "deployment_weather",
+                "base_url": "https://{deployment}.example/chat",
+                "daily_limit": 5,
+                "prompts": ["What is the weather today?"],
+                "answers": [{ "question": "Is it raining?", "answer": "No" }]
+            },
+            { "name": "Off", "deployment_id": "deployment_off", "enabled": false }
+        ]"#,
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(agents.len(), 3);
+    assert_eq!(agents[0].name, "Professor");
+    assert!(!agents[0].messages.is_empty());
+    assert_eq!(agents[1].name, "Weather");
+    assert_eq!(agents[1].key(), "WEATHER");
+    assert_eq!(agents[1].daily_limit, Some(5));
+    assert_eq!(
+        agents[1].endpoint(),
+        "https://deployment-weather.example/chat"
+    );
+    assert!(!agents[2].enabled);
+    assert!(agents[2].messages.is_empty());
+}
+
+#[test]
+fn invalid_agents_are_rejected() {
+    for data in [
+        "{}",
+        r#"[{ "name": "No Id", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[{ "name": "Blank", "deployment_id": "  ", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[{ "name": "Spaced", "deployment_id": "deployment one", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[{ "name": "Bad Url", "deployment_id": "deployment_a", "base_url": "ftp://{deployment}.example", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[{ "name": "No Prompts", "deployment_id": "deployment_a", "prompts": [], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[{ "name": "Blank Prompts", "deployment_id": "deployment_a", "prompts": [" "], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[{ "name": "No Answers", "deployment_id": "deployment_a", "prompts": ["hi"], "answers": [] }]"#,
+        r#"[{ "deployment_id": "deployment_unknown", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] }]"#,
+        r#"[
+            { "name": "A", "deployment_id": "deployment_a", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] },
+            { "name": "B", "deployment_id": "deployment_a", "prompts": ["hi"], "answers": [{ "question": "q", "answer": "a" }] }
+        ]"#,
+        r#"[{ "name": "Off", "deployment_id": "deployment_a", "enabled": false }]"#,
+    ] {
+        assert!(Agent::parse(data, false).is_err(), "{}", data);
+    }
+}
+
+#[test]
+fn canned_answers_are_only_required_outside_real_mode() {
+    let data = r#"[{ "name": "Live", "deployment_id": "deployment_live", "prompts": ["hi"] }]"#;
+
+    assert!(Agent::parse(data, false).is_err());
+    assert_eq!(Agent::parse(data, true).unwrap()[0].name, "Live");
+}
@@ -0,0 +1,38 @@
+use kite_ai_bot::utils::network::Network;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+pub async fn serve(respond: fn(&str) -> Option<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = [0u8; 4096];
+            let read = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..read]).to_string();
+            let Some(body) = respond(&request) else {
+                continue;
+            };
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    format!("http://{}", addr)
+}
+
+pub fn network(rpc_urls: Vec<String>) -> Network {
+    Network {
+        name: "local".to_string(),
+        chain_id: 2368,
+        rpc_urls,
+        explorer: String::new(),
+        symbol: "ETH".to_string(),
+        tokens: vec![],
+    }
+}
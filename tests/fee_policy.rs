@@ -0,0 +1,101 @@
+use ethers::types::U256;
+use kite_ai_bot::service::evm_service::fee_policy::{FeePolicy, TxFees};
+
+fn gwei(value: &str) -> U256 {
+    FeePolicy::parse_gwei(value).unwrap()
+}
+
+#[test]
+fn eip1559_fees_use_median_reward_and_double_base_fee() {
+    let policy = FeePolicy::default();
+    let fees = policy
+        .eip1559_fees(gwei("1"), &[gwei("0.5"), gwei("0.1"), gwei("3")])
+        .unwrap();
+
+    assert_eq!(
+        fees,
+        TxFees::Eip1559 {
+            max_fee_per_gas: gwei("2.5"),
+            max_priority_fee_per_gas: gwei("0.5"),
+        }
+    );
+}
+
+#[test]
+fn eip1559_fees_are_capped() {
+    let policy = FeePolicy {
+        max_fee_per_gas: gwei("10"),
+        max_priority_fee_per_gas: gwei("1"),
+        ..FeePolicy::default()
+    };
+
+    let fees = policy.eip1559_fees(gwei("8"), &[gwei("5")]).unwrap();
+    assert_eq!(
+        fees,
+        TxFees::Eip1559 {
+            max_fee_per_gas: gwei("10"),
+            max_priority_fee_per_gas: gwei("1"),
+        }
+    );
+
+    assert!(policy.eip1559_fees(gwei("9.5"), &[]).is_err());
+}
+
+#[test]
+fn legacy_fees_fall_back_to_configured_price() {
+    let policy = FeePolicy::default();
+
+    assert_eq!(
+        policy.legacy_fees(None).unwrap(),
+        TxFees::Legacy {
+            gas_price: gwei("5")
+        }
+    );
+    assert_eq!(
+        policy.legacy_fees(Some(gwei("1"))).unwrap(),
+        TxFees::Legacy {
+            gas_price: gwei("1")
+        }
+    );
+    assert!(policy.legacy_fees(Some(gwei("101"))).is_err());
+}
+
+#[test]
+fn gas_limit_applies_multiplier() {
+    let policy = FeePolicy::default();
+
+    assert_eq!(policy.gas_limit(U256::from(21000)), U256::from(25200));
+    assert!(FeePolicy::parse_gwei("abc").is_err());
+}
+
+#[test]
+fn replacement_fees_are_bumped_within_cap() {
+    let policy = FeePolicy::default();
+    let current = TxFees::Eip1559 {
+        max_fee_per_gas: gwei("10"),
+        max_priority_fee_per_gas: gwei("1"),
+    };
+
+    let fees = policy.replacement_fees(current, None).unwrap();
+    assert_eq!(
+        fees,
+        TxFees::Eip1559 {
+            max_fee_per_gas: gwei("11.5") + 1,
+            max_priority_fee_per_gas: gwei("1.15") + 1,
+        }
+    );
+
+    let estimate = TxFees::Eip1559 {
+        max_fee_per_gas: gwei("20"),
+        max_priority_fee_per_gas: gwei("2"),
+    };
+    assert_eq!(
+        policy.replacement_fees(current, Some(estimate)).unwrap(),
+        estimate
+    );
+
+    let legacy = TxFees::Legacy {
+        gas_price: gwei("90"),
+    };
+    assert!(policy.replacement_fees(legacy, None).is_err());
+}
@@ -0,0 +1,12 @@
+use kite_ai_bot::utils::helper::Helper;
+
+#[test]
+fn read_optional_treats_only_missing_files_as_absent() {
+    assert_eq!(
+        Helper::read_optional("does-not-exist/config.json").unwrap(),
+        None
+    );
+
+    let dir = std::env::temp_dir();
+    assert!(Helper::read_optional(dir.to_str().unwrap()).is_err());
+}
@@ -0,0 +1,93 @@
+use kite_ai_bot::service::db::migration::{self, Migration, MIGRATIONS};
+use rusqlite::Connection;
+
+fn legacy_connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            address TEXT NOT NULL,
+            tx_type TEXT NOT NULL,
+            date TEXT NOT NULL
+        )",
+        [],
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO log (address, tx_type, date) VALUES ('0xabc', 'interact', '2025-02-14 10:00:00')",
+        [],
+    )
+    .unwrap();
+    conn
+}
+
+fn table_exists(conn: &Connection, table: &str) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get::<_, i64>(0),
+    )
+    .unwrap()
+        == 1
+}
+
+#[test]
+fn migrates_fresh_database_to_latest() {
+    let mut conn = Connection::open_in_memory().unwrap();
+
+    let version = migration::migrate(&mut conn).unwrap();
+
+    assert_eq!(version, migration::latest_version());
+    assert!(table_exists(&conn, "log"));
+    assert!(table_exists(&conn, "interactions"));
+}
+
+#[test]
+fn upgrades_legacy_database_and_keeps_rows() {
+    let mut conn = legacy_connection();
+    assert_eq!(migration::current_version(&conn).unwrap(), 0);
+
+    let version = migration::migrate(&mut conn).unwrap();
+
+    assert_eq!(version, migration::latest_version());
+    assert!(table_exists(&conn, "interactions"));
+    let logs: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM log WHERE address = '0xabc'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(logs, 1);
+}
+
+#[test]
+fn migrate_is_idempotent() {
+    let mut conn = legacy_connection();
+
+    migration::migrate(&mut conn).unwrap();
+    let version = migration::migrate(&mut conn).unwrap();
+
+    assert_eq!(version, migration::latest_version());
+    let applied: i64 = conn
+        .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(applied, MIGRATIONS.len() as i64);
+}
+
+#[test]
+fn failed_migration_is_rolled_back() {
+    let mut conn = legacy_connection();
+    migration::migrate(&mut conn).unwrap();
+    let latest = migration::latest_version();
+
+    let broken = [Migration {
+        version: latest + 1,
+        name: "broken",
+        sql: "CREATE TABLE partial (id INTEGER); INSERT INTO missing_table VALUES (1);",
+    }];
+    assert!(migration::apply(&mut conn, &broken).is_err());
+
+    assert_eq!(migration::current_version(&conn).unwrap(), latest);
+    assert!(!table_exists(&conn, "partial"));
+}
@@ -0,0 +1,66 @@
+use ethers::signers::Signer;
+use kite_ai_bot::utils::{mnemonic_account::MnemonicAccount, wallet_key::WalletKey};
+
+const PHRASE: &str = "test test test test test test test test test test test junk";
+
+#[test]
+fn derivation_ranges_expand_into_accounts() {
+    let accounts = MnemonicAccount::expand(&format!("{} m/44'/60'/0'/0/0..2", PHRASE)).unwrap();
+    assert_eq!(
+        accounts,
+        vec![
+            format!("{} m/44'/60'/0'/0/0", PHRASE),
+            format!("{} m/44'/60'/0'/0/1", PHRASE),
+            format!("{} m/44'/60'/0'/0/2", PHRASE),
+        ]
+    );
+
+    let addresses: Vec<String> = accounts
+        .iter()
+        .map(|account| format!("{:?}", WalletKey::to_wallet(account).unwrap().address()))
+        .collect();
+    assert_eq!(
+        addresses,
+        vec![
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+            "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+        ]
+    );
+
+    assert_eq!(
+        MnemonicAccount::expand(&format!("{} 4..5", PHRASE)).unwrap()[1],
+        format!("{} m/44'/60'/0'/0/5", PHRASE)
+    );
+    assert_eq!(
+        MnemonicAccount::expand(PHRASE).unwrap(),
+        vec![PHRASE.to_string()]
+    );
+    assert_eq!(
+        MnemonicAccount::expand("0xabc").unwrap(),
+        vec!["0xabc".to_string()]
+    );
+}
+
+#[test]
+fn single_accounts_are_parsed() {
+    let account = MnemonicAccount::parse(&format!("{} m/44'/60'/1'/0/7", PHRASE)).unwrap();
+    assert_eq!(account.phrase, PHRASE);
+    assert_eq!(account.path.as_deref(), Some("m/44'/60'/1'/0/7"));
+    assert_eq!(MnemonicAccount::parse(PHRASE).unwrap().path, None);
+    assert!(MnemonicAccount::parse(&format!("{} 0..1", PHRASE)).is_err());
+}
+
+#[test]
+fn invalid_mnemonics_and_paths_are_rejected() {
+    for entry in [
+        "test test test test test test test test test test test test".to_string(),
+        "these are not valid bip words at all here".to_string(),
+        format!("{} m/44'/60'/x/0/0", PHRASE),
+        format!("{} m/44'/60'/0'/0/5..1", PHRASE),
+        format!("{} 0..1000", PHRASE),
+        format!("{} m/44'/60'/0'/0/2147483648", PHRASE),
+    ] {
+        assert!(MnemonicAccount::expand(&entry).is_err(), "{}", entry);
+    }
+}
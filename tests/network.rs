@@ -0,0 +1,50 @@
+use kite_ai_bot::utils::network::Network;
+
+#[test]
+fn networks_are_parsed_and_normalized() {
+    let networks = Network::parse(
+        r#"[
+            {
+                "name": "kite-testnet",
+                "chain_id": 2368,
+                "rpc_urls": ["https://rpc-testnet.gokite.ai", " https://backup.example "],
+                "explorer": "https://testnet.kitescan.ai",
+                "symbol": "KITE",
+                "tokens": [" 0x0000000000000000000000000000000000000ABC "]
+            },
+            { "name": "local", "chain_id": 31337, "rpc_urls": ["http://127.0.0.1:8545"] }
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(networks.len(), 2);
+    assert_eq!(networks[0].rpc_urls[1], "https://backup.example");
+    assert_eq!(networks[0].explorer, "https://testnet.kitescan.ai/");
+    assert_eq!(networks[1].symbol, "ETH");
+    assert_eq!(
+        networks[0].tokens,
+        vec!["0x0000000000000000000000000000000000000abc".to_string()]
+    );
+    assert!(networks[1].tokens.is_empty());
+    assert_eq!(
+        Network::find(&networks, "LOCAL").map(|network| network.chain_id),
+        Some(31337)
+    );
+}
+
+#[test]
+fn invalid_networks_are_rejected() {
+    for data in [
+        "[]",
+        r#"[{ "name": "a", "rpc_urls": ["https://a.example"] }]"#,
+        r#"[{ "name": "a", "chain_id": 1, "rpc_urls": [] }]"#,
+        r#"[{ "name": "a", "chain_id": 1, "rpc_urls": ["ws://a.example"] }]"#,
+        r#"[{ "name": "a", "chain_id": 1, "rpc_urls": ["https://a.example"], "tokens": ["0x12"] }]"#,
+        r#"[
+            { "name": "a", "chain_id": 1, "rpc_urls": ["https://a.example"] },
+            { "name": "A", "chain_id": 2, "rpc_urls": ["https://b.example"] }
+        ]"#,
+    ] {
+        assert!(Network::parse(data).is_err(), "{}", data);
+    }
+}
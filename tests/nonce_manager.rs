@@ -0,0 +1,59 @@
+use ethers::types::{transaction::eip2718::TypedTransaction, TransactionRequest, H256, U256};
+use kite_ai_bot::service::evm_service::nonce_manager::NonceManager;
+use std::time::Duration;
+
+fn tx(nonce: u64) -> TypedTransaction {
+    TransactionRequest::new().nonce(nonce).into()
+}
+
+#[test]
+fn reserve_hands_out_sequential_nonces_while_in_flight() {
+    let mut manager = NonceManager::default();
+
+    assert_eq!(manager.reserve(U256::from(5)), U256::from(5));
+    manager.track(U256::from(5), H256::zero(), tx(5));
+    assert_eq!(manager.reserve(U256::from(5)), U256::from(6));
+    assert_eq!(manager.reserve(U256::from(9)), U256::from(9));
+}
+
+#[test]
+fn release_returns_unused_nonce() {
+    let mut manager = NonceManager::default();
+
+    let nonce = manager.reserve(U256::from(3));
+    manager.track(nonce, H256::zero(), tx(3));
+    manager.release(nonce);
+
+    assert_eq!(manager.in_flight(), 0);
+    assert_eq!(manager.reserve(U256::from(3)), U256::from(3));
+}
+
+#[test]
+fn sync_drops_mined_transactions() {
+    let mut manager = NonceManager::default();
+
+    for _ in 0..3 {
+        let nonce = manager.reserve(U256::zero());
+        manager.track(nonce, H256::zero(), tx(nonce.as_u64()));
+    }
+
+    let mined = manager.sync(U256::from(2));
+    assert_eq!(mined.len(), 2);
+    assert_eq!(manager.in_flight(), 1);
+    assert!(manager.get(U256::from(2)).is_some());
+}
+
+#[test]
+fn replaced_transactions_are_counted_and_reported_as_stuck() {
+    let mut manager = NonceManager::default();
+
+    let nonce = manager.reserve(U256::zero());
+    manager.track(nonce, H256::zero(), tx(0));
+    manager.track(nonce, H256::repeat_byte(1), tx(0));
+
+    let pending = manager.get(nonce).unwrap();
+    assert_eq!(pending.replacements, 1);
+    assert_eq!(pending.hash, H256::repeat_byte(1));
+    assert_eq!(manager.stuck(Duration::ZERO).len(), 1);
+    assert!(manager.stuck(Duration::from_secs(3600)).is_empty());
+}
@@ -0,0 +1,84 @@
+use ethers::types::Bytes;
+use kite_ai_bot::model::onchain_action::{ActionKind, ActionPlan};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+#[test]
+fn action_plans_are_parsed_per_account() {
+    let plan = ActionPlan::parse(
+        r#"{
+            "default": [{ "name": "self", "type": "self_transfer" }],
+            "accounts": {
+                "2": [
+                    { "name": "fund", "type": "wallet_transfer", "to": "account:1", "amount": "0.01", "daily_count": 2 },
+                    { "name": "token", "type": "token_transfer", "enabled": false }
+                ],
+                "0x0000000000000000000000000000000000000ABC": [
+                    { "name": "ping", "type": "raw_calldata", "to": "0x0000000000000000000000000000000000000001", "data": "0x5c36b186" }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        plan.for_account(1, "0x01")[0].kind,
+        ActionKind::SelfTransfer
+    );
+    assert_eq!(plan.for_account(1, "0x01")[0].amount, Decimal::ZERO);
+
+    let actions = plan.for_account(2, "0x01");
+    assert_eq!(actions.len(), 2);
+    assert_eq!(actions[0].amount, Decimal::from_str("0.01").unwrap());
+    assert_eq!(actions[0].daily_count, Some(2));
+    assert_eq!(actions[0].log_type(), "onchain:fund");
+    assert_eq!(
+        ActionKind::account_ref(actions[0].kind.target().unwrap()),
+        Some(1)
+    );
+    assert!(!actions[1].enabled);
+
+    let actions = plan.for_account(3, "0x0000000000000000000000000000000000000abc");
+    assert_eq!(actions[0].name, "ping");
+}
+
+#[test]
+fn contract_calls_are_abi_encoded() {
+    let kind = ActionKind::ContractCall {
+        to: "0x0000000000000000000000000000000000000001".to_string(),
+        function: "transfer(address,uint256)".to_string(),
+        args: vec![
+            "0x0000000000000000000000000000000000000002".to_string(),
+            "1000".to_string(),
+        ],
+    };
+
+    let expected = Bytes::from_str(
+        "0xa9059cbb\
+         0000000000000000000000000000000000000000000000000000000000000002\
+         00000000000000000000000000000000000000000000000000000000000003e8",
+    )
+    .unwrap();
+    assert_eq!(kind.calldata().unwrap(), Some(expected));
+    assert_eq!(ActionKind::SelfTransfer.calldata().unwrap(), None);
+}
+
+#[test]
+fn invalid_actions_are_rejected() {
+    for data in [
+        r#"{ "default": [{ "type": "self_transfer" }] }"#,
+        r#"{ "default": [{ "name": "a", "type": "unknown" }] }"#,
+        r#"{ "default": [{ "name": "a", "type": "self_transfer", "amount": "-1" }] }"#,
+        r#"{ "default": [{ "name": "a", "type": "wallet_transfer", "to": "account:0" }] }"#,
+        r#"{ "default": [{ "name": "a", "type": "token_transfer", "token": "0x12" }] }"#,
+        r#"{ "default": [{ "name": "a", "type": "raw_calldata", "to": "0x0000000000000000000000000000000000000001", "data": "0xzz" }] }"#,
+        r#"{ "default": [{ "name": "a", "type": "contract_call", "to": "0x0000000000000000000000000000000000000001", "function": "approve(address,uint256)", "args": ["0x02"] }] }"#,
+        r#"{ "default": [
+            { "name": "a", "type": "self_transfer" },
+            { "name": "A", "type": "self_transfer" }
+        ] }"#,
+        r#"{ "accounts": { "first": [{ "name": "a", "type": "self_transfer" }] } }"#,
+    ] {
+        assert!(ActionPlan::parse(data).is_err(), "{}", data);
+    }
+}
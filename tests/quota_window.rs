@@ -0,0 +1,98 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use kite_ai_bot::utils::quota_window::QuotaWindow;
+
+fn utc(value: &str) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&naive(value))
+}
+
+fn naive(value: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+}
+
+#[test]
+fn utc_window_resets_at_midnight() {
+    let window = QuotaWindow::default();
+
+    assert_eq!(
+        window.bounds(utc("2025-02-14 23:59:59")),
+        (naive("2025-02-14 00:00:00"), naive("2025-02-15 00:00:00"))
+    );
+    assert_eq!(
+        window.bounds(utc("2025-02-15 00:00:00")),
+        (naive("2025-02-15 00:00:00"), naive("2025-02-16 00:00:00"))
+    );
+}
+
+#[test]
+fn positive_offset_resets_before_utc_midnight() {
+    let window = QuotaWindow::new(QuotaWindow::parse_offset("+07:00").unwrap(), 0).unwrap();
+
+    assert_eq!(
+        window.bounds(utc("2025-02-14 16:59:59")),
+        (naive("2025-02-13 17:00:00"), naive("2025-02-14 17:00:00"))
+    );
+    assert_eq!(
+        window.bounds(utc("2025-02-14 17:00:00")),
+        (naive("2025-02-14 17:00:00"), naive("2025-02-15 17:00:00"))
+    );
+}
+
+#[test]
+fn negative_offset_resets_after_utc_midnight() {
+    let window = QuotaWindow::new(QuotaWindow::parse_offset("-05:00").unwrap(), 0).unwrap();
+
+    assert_eq!(
+        window.bounds(utc("2025-02-15 04:59:59")),
+        (naive("2025-02-14 05:00:00"), naive("2025-02-15 05:00:00"))
+    );
+    assert_eq!(
+        window.bounds(utc("2025-02-15 05:00:00")),
+        (naive("2025-02-15 05:00:00"), naive("2025-02-16 05:00:00"))
+    );
+}
+
+#[test]
+fn reset_hour_is_applied_in_local_time() {
+    let window = QuotaWindow::new(QuotaWindow::parse_offset("+08:00").unwrap(), 8).unwrap();
+
+    assert_eq!(
+        window.bounds(utc("2025-02-14 23:59:59")),
+        (naive("2025-02-14 00:00:00"), naive("2025-02-15 00:00:00"))
+    );
+    assert_eq!(
+        window.bounds(utc("2025-02-15 00:00:00")),
+        (naive("2025-02-15 00:00:00"), naive("2025-02-16 00:00:00"))
+    );
+}
+
+#[test]
+fn parses_offset_formats() {
+    assert_eq!(QuotaWindow::parse_offset("+07:00"), Ok(420));
+    assert_eq!(QuotaWindow::parse_offset("-0530"), Ok(-330));
+    assert_eq!(QuotaWindow::parse_offset("UTC+9"), Ok(540));
+    assert_eq!(QuotaWindow::parse_offset("Z"), Ok(0));
+    assert!(QuotaWindow::parse_offset("+7:75").is_err());
+    assert!(QuotaWindow::parse_offset("Asia/Jakarta").is_err());
+}
+
+#[test]
+fn rejects_out_of_range_values() {
+    assert!(QuotaWindow::new(15 * 60, 0).is_err());
+    assert!(QuotaWindow::new(0, 24).is_err());
+    assert!(QuotaWindow::parse_offset("+1é1").is_err());
+    assert!(QuotaWindow::parse_offset("é123").is_err());
+}
+
+#[test]
+fn next_reset_is_end_of_current_window() {
+    let window = QuotaWindow::new(QuotaWindow::parse_offset("+07:00").unwrap(), 0).unwrap();
+
+    assert_eq!(
+        window.next_reset(utc("2025-02-14 16:00:00")),
+        utc("2025-02-14 17:00:00")
+    );
+    assert_eq!(
+        window.next_reset(utc("2025-02-14 17:00:00")),
+        utc("2025-02-15 17:00:00")
+    );
+}
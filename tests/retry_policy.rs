@@ -0,0 +1,118 @@
+use kite_ai_bot::service::api::{api_service::HttpMethod, retry_policy::RetryPolicy};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, RETRY_AFTER},
+    StatusCode,
+};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+fn policy(jitter: f64) -> RetryPolicy {
+    RetryPolicy {
+        base_delay_ms: 100,
+        max_delay_ms: 1000,
+        jitter,
+        ..RetryPolicy::default()
+    }
+}
+
+#[test]
+fn delay_grows_exponentially_and_is_capped() {
+    let policy = policy(0.0);
+
+    assert_eq!(policy.delay(1, None), Duration::from_millis(100));
+    assert_eq!(policy.delay(2, None), Duration::from_millis(200));
+    assert_eq!(policy.delay(4, None), Duration::from_millis(800));
+    assert_eq!(policy.delay(5, None), Duration::from_millis(1000));
+    assert_eq!(policy.delay(64, None), Duration::from_millis(1000));
+    assert_eq!(
+        policy.delay(1, Some(Duration::from_secs(60))),
+        Duration::from_millis(1000)
+    );
+    assert_eq!(
+        policy.delay(3, Some(Duration::from_millis(250))),
+        Duration::from_millis(250)
+    );
+}
+
+#[test]
+fn delay_jitter_stays_within_bounds() {
+    let policy = policy(0.5);
+
+    for _ in 0..100 {
+        let delay = policy.delay(2, None).as_millis();
+        assert!((100..=300).contains(&delay), "{}", delay);
+        assert!(policy.delay(10, None) <= Duration::from_millis(1000));
+    }
+}
+
+#[test]
+fn retry_after_header_is_parsed() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(RetryPolicy::retry_after(&headers), None);
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static(" 7 "));
+    assert_eq!(
+        RetryPolicy::retry_after(&headers),
+        Some(Duration::from_secs(7))
+    );
+
+    let date = (chrono::Utc::now() + chrono::Duration::seconds(30)).to_rfc2822();
+    headers.insert(RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+    let delay = RetryPolicy::retry_after(&headers).unwrap();
+    assert!(delay > Duration::from_secs(25) && delay <= Duration::from_secs(30));
+
+    let past = (chrono::Utc::now() - chrono::Duration::seconds(30)).to_rfc2822();
+    headers.insert(RETRY_AFTER, HeaderValue::from_str(&past).unwrap());
+    assert_eq!(RetryPolicy::retry_after(&headers), None);
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+    assert_eq!(RetryPolicy::retry_after(&headers), None);
+}
+
+#[test]
+fn post_is_not_retried_on_status_unless_opted_in() {
+    let policy = RetryPolicy::default();
+    let post = HttpMethod::POST.is_idempotent();
+
+    assert!(HttpMethod::GET.is_idempotent());
+    assert!(!post);
+    assert!(policy.is_retryable_status(StatusCode::BAD_GATEWAY, true));
+    assert!(!policy.is_retryable_status(StatusCode::BAD_GATEWAY, post));
+    assert!(!policy.is_retryable_status(StatusCode::BAD_REQUEST, true));
+
+    let opted_in = RetryPolicy {
+        retry_non_idempotent: true,
+        ..RetryPolicy::default()
+    };
+    assert!(opted_in.is_retryable_status(StatusCode::BAD_GATEWAY, post));
+}
+
+#[tokio::test]
+async fn post_is_retried_on_connect_errors_only() {
+    let policy = RetryPolicy::default();
+    let client = reqwest::Client::new();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let refused = client.post(&url).send().await.unwrap_err();
+    assert!(policy.is_retryable_error(&refused, false));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        drop(socket);
+    });
+    let timed_out = client
+        .post(&url)
+        .timeout(Duration::from_millis(200))
+        .send()
+        .await
+        .unwrap_err();
+    server.abort();
+
+    assert!(!policy.is_retryable_error(&timed_out, false));
+    assert!(policy.is_retryable_error(&timed_out, true));
+}
@@ -0,0 +1,160 @@
+use ethers::{
+    providers::{JsonRpcClient, Middleware, Provider},
+    types::{Bytes, H256},
+    utils::keccak256,
+};
+use kite_ai_bot::service::evm_service::rpc_pool::{EndpointHealth, RpcHealthPolicy, RpcPool};
+use std::time::Duration;
+
+mod common;
+
+use common::{network, serve};
+
+fn health(latency_ms: u64, block_number: u64, failures: usize) -> EndpointHealth {
+    let mut health = EndpointHealth {
+        block_number: Some(block_number),
+        ..EndpointHealth::default()
+    };
+    for idx in 0..10 {
+        health.record(idx >= failures, Some(Duration::from_millis(latency_ms)));
+    }
+    health
+}
+
+#[test]
+fn rank_prefers_healthy_then_reliable_endpoints() {
+    let policy = RpcHealthPolicy::default();
+    let endpoints = [
+        health(300, 100, 0),
+        health(50, 90, 0),
+        health(80, 100, 8),
+        health(60, 100, 0),
+        health(120, 99, 1),
+    ];
+
+    assert_eq!(policy.rank(&endpoints), vec![3, 0, 4, 1, 2]);
+}
+
+#[test]
+fn error_rate_uses_recent_outcomes() {
+    let mut health = EndpointHealth::default();
+    assert_eq!(health.error_rate(), 0.0);
+
+    for _ in 0..20 {
+        health.record(false, None);
+    }
+    for _ in 0..10 {
+        health.record(true, None);
+    }
+    assert_eq!(health.error_rate(), 0.5);
+}
+
+#[tokio::test]
+async fn broadcast_skips_endpoints_on_another_chain() {
+    let wrong_chain = serve(|request| {
+        Some(if request.contains("eth_chainId") {
+            r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#.to_string()
+        } else {
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"{:#x}"}}"#,
+                H256::repeat_byte(1)
+            )
+        })
+    })
+    .await;
+    let right_chain = serve(|request| {
+        Some(if request.contains("eth_chainId") {
+            r#"{"jsonrpc":"2.0","id":1,"result":"0x940"}"#.to_string()
+        } else {
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"{:#x}"}}"#,
+                H256::repeat_byte(2)
+            )
+        })
+    })
+    .await;
+
+    let pool = RpcPool::new(
+        &network(vec![wrong_chain, right_chain]),
+        None,
+        RpcHealthPolicy::default(),
+    )
+    .unwrap();
+    assert!(pool.verify_chain_id().await.is_err());
+
+    let hash: H256 = pool
+        .request("eth_sendRawTransaction", [Bytes::from(vec![0x01])])
+        .await
+        .unwrap();
+    assert_eq!(hash, H256::repeat_byte(2));
+}
+
+fn chain_id_or(request: &str, response: Option<&str>) -> Option<String> {
+    if request.contains("eth_chainId") {
+        return Some(r#"{"jsonrpc":"2.0","id":1,"result":"0x940"}"#.to_string());
+    }
+    response.map(str::to_string)
+}
+
+#[tokio::test]
+async fn broadcast_failover_accepts_already_known_transaction() {
+    let dropped = serve(|request| chain_id_or(request, None)).await;
+    let known = serve(|request| {
+        chain_id_or(
+            request,
+            Some(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"already known"}}"#),
+        )
+    })
+    .await;
+
+    let pool = RpcPool::new(
+        &network(vec![dropped, known.to_owned()]),
+        None,
+        RpcHealthPolicy::default(),
+    )
+    .unwrap();
+    let hash: H256 = pool
+        .request("eth_sendRawTransaction", [Bytes::from(vec![0x01])])
+        .await
+        .unwrap();
+    assert_eq!(hash, H256::from(keccak256([0x01])));
+
+    let pool = RpcPool::new(&network(vec![known]), None, RpcHealthPolicy::default()).unwrap();
+    assert!(pool
+        .request::<_, H256>("eth_sendRawTransaction", [Bytes::from(vec![0x01])])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn unreachable_network_is_not_verified() {
+    let pool = RpcPool::new(
+        &network(vec!["http://127.0.0.1:1".to_string()]),
+        None,
+        RpcHealthPolicy::default(),
+    )
+    .unwrap();
+
+    assert!(pool.verify_chain_id().await.is_err());
+    assert!(pool
+        .request::<_, H256>("eth_sendRawTransaction", [Bytes::from(vec![0x01])])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn requests_fail_over_to_next_endpoint() {
+    let live = serve(|_| Some(r#"{"jsonrpc":"2.0","id":1,"result":"0x940"}"#.to_string())).await;
+
+    let pool = RpcPool::new(
+        &network(vec!["http://127.0.0.1:1".to_string(), live.to_owned()]),
+        None,
+        RpcHealthPolicy::default(),
+    )
+    .unwrap();
+    let provider = Provider::new(pool);
+
+    assert_eq!(provider.get_chainid().await.unwrap().as_u64(), 2368);
+    assert!(RpcPool::health("http://127.0.0.1:1").error_rate() > 0.0);
+    assert_eq!(provider.as_ref().active_url(), live);
+}
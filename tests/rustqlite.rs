@@ -0,0 +1,182 @@
+use kite_ai_bot::{
+    model::agent::Agent,
+    service::db::rustqlite::{
+        NewInteraction, NewTransaction, RustQLite, TransactionReceiptUpdate, MEMORY_PATH,
+    },
+};
+
+#[tokio::test]
+async fn logs_are_counted_per_address_and_type() {
+    let db = RustQLite::open(MEMORY_PATH).unwrap();
+
+    db.insert_log("0xabc", "interact").await.unwrap();
+    db.insert_log("0xabc", "interact").await.unwrap();
+    db.insert_log("0xabc", "transfer").await.unwrap();
+    db.insert_log("0xdef", "interact").await.unwrap();
+
+    assert_eq!(
+        db.get_logs_today("0xabc", "interact").await.unwrap().len(),
+        2
+    );
+    assert_eq!(
+        db.get_logs_today("0xdef", "interact").await.unwrap().len(),
+        1
+    );
+    assert_eq!(db.get_all_logs().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn missing_log_returns_error() {
+    let db = RustQLite::open(MEMORY_PATH).unwrap();
+
+    assert!(db.update_log(42, "0xabc", "interact").await.is_err());
+    assert!(db.delete_log(42).await.is_err());
+}
+
+#[tokio::test]
+async fn interactions_are_listed_and_deduplicated() {
+    let db = RustQLite::open(MEMORY_PATH).unwrap();
+
+    for _ in 0..2 {
+        let id = db
+            .insert_interaction(NewInteraction {
+                address: "0xabc",
+                agent_id: "deployment_a",
+                request_text: "What is Kite AI?",
+                response_text: Some("An AI chain."),
+                http_status: Some(200),
+                latency_ms: Some(120),
+                report_status: "pending",
+            })
+            .await
+            .unwrap();
+        db.update_interaction_report(id, "reported").await.unwrap();
+    }
+
+    let history = db.get_interactions("0xabc", 10).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert!(history.iter().all(|item| item.report_status == "reported"));
+    assert_eq!(
+        db.get_requests_today("0xabc", "deployment_a")
+            .await
+            .unwrap(),
+        vec!["What is Kite AI?".to_string()]
+    );
+    assert!(db
+        .get_requests_today("0xabc", "deployment_b")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn interaction_budget_respects_global_and_agent_limits() {
+    let db = RustQLite::open(MEMORY_PATH).unwrap();
+    let mut agents = Agent::defaults();
+    Agent::apply_daily_limits(&mut agents, "PROFESSOR=1").unwrap();
+
+    for status in ["reported", "skipped"] {
+        db.insert_interaction(NewInteraction {
+            address: "0xabc",
+            agent_id: &agents[0].id,
+            request_text: "What is Kite AI?",
+            response_text: Some("An AI chain."),
+            http_status: Some(200),
+            latency_ms: Some(120),
+            report_status: status,
+        })
+        .await
+        .unwrap();
+    }
+    db.insert_log("0xabc", "interact").await.unwrap();
+
+    let budget = db.get_interaction_budget("0xabc", 2).await.unwrap();
+    assert_eq!(budget.global_remaining(), 1);
+    assert_eq!(budget.used_by(&agents[0]), 1);
+    assert_eq!(budget.remaining_for(&agents[0]), 0);
+    assert_eq!(budget.remaining_for(&agents[1]), 1);
+
+    db.insert_log("0xabc", "interact").await.unwrap();
+    let budget = db.get_interaction_budget("0xabc", 2).await.unwrap();
+    assert!(budget.is_exhausted(&agents.iter().collect::<Vec<_>>()));
+}
+
+#[tokio::test]
+async fn interaction_budget_is_not_exhausted_without_agents() {
+    let db = RustQLite::open(MEMORY_PATH).unwrap();
+
+    let budget = db.get_interaction_budget("0xabc", 2).await.unwrap();
+    assert!(!budget.is_exhausted(&[]));
+}
+
+#[tokio::test]
+async fn transactions_are_recorded_with_receipts() {
+    let db = RustQLite::open(MEMORY_PATH).unwrap();
+
+    for (hash, nonce, chain_id) in [
+        ("0x01", 0, 2368),
+        ("0x02", 1, 2368),
+        ("0x03", 1, 2368),
+        ("0x05", 0, 1),
+    ] {
+        db.insert_transaction(NewTransaction {
+            hash,
+            address: "0xabc",
+            nonce,
+            chain_id,
+            to_address: Some("0xabc"),
+            value: "0",
+        })
+        .await
+        .unwrap();
+    }
+
+    for hash in ["0x01", "0x03", "0x05"] {
+        db.update_transaction_receipt(
+            hash,
+            TransactionReceiptUpdate {
+                status: "success",
+                gas_used: Some(21000),
+                effective_gas_price: Some("1000000000"),
+                block_number: Some(10),
+            },
+        )
+        .await
+        .unwrap();
+    }
+    db.update_transaction_status("0x02", "replaced")
+        .await
+        .unwrap();
+    assert!(db
+        .update_transaction_status("0x04", "dropped")
+        .await
+        .is_err());
+
+    let history = db.get_transactions("0xabc", 10).await.unwrap();
+    assert_eq!(
+        history
+            .iter()
+            .map(|tx| (tx.hash.as_str(), tx.status.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("0x05", "success"),
+            ("0x03", "success"),
+            ("0x02", "replaced"),
+            ("0x01", "success")
+        ]
+    );
+    assert_eq!(history[0].block_number, Some(10));
+    assert_eq!(history[1].chain_id, Some(2368));
+    assert_eq!(
+        db.get_total_gas_spent("0xabc")
+            .await
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![
+            (Some(1), 21000 * 1_000_000_000),
+            (Some(2368), 2 * 21000 * 1_000_000_000)
+        ]
+    );
+    assert!(db.get_total_gas_spent("0xdef").await.unwrap().is_empty());
+}
@@ -0,0 +1,109 @@
+use ethers::{
+    abi::{encode, Token},
+    providers::JsonRpcError,
+    types::U256,
+};
+use kite_ai_bot::service::evm_service::simulation::{decode_revert, SimulationError};
+use serde_json::json;
+
+fn with_selector(selector: [u8; 4], tokens: &[Token]) -> Vec<u8> {
+    let mut data = selector.to_vec();
+    data.extend(encode(tokens));
+    data
+}
+
+#[test]
+fn revert_data_is_decoded() {
+    let reason = with_selector(
+        [0x08, 0xc3, 0x79, 0xa0],
+        &[Token::String(
+            "ERC20: transfer amount exceeds balance".to_string(),
+        )],
+    );
+    assert_eq!(
+        decode_revert(&reason),
+        "ERC20: transfer amount exceeds balance"
+    );
+
+    let panic = with_selector([0x4e, 0x48, 0x7b, 0x71], &[Token::Uint(U256::from(0x11))]);
+    assert_eq!(
+        decode_revert(&panic),
+        "panic 0x11 (arithmetic overflow or underflow)"
+    );
+
+    assert_eq!(
+        decode_revert(&[0xde, 0xad, 0xbe, 0xef]),
+        "custom error 0xdeadbeef"
+    );
+    assert_eq!(decode_revert(&[]), "execution reverted");
+}
+
+#[test]
+fn rpc_errors_are_classified() {
+    let reverted = JsonRpcError {
+        code: 3,
+        message: "execution reverted".to_string(),
+        data: Some(json!(format!(
+            "0x{}",
+            ethers::utils::hex::encode(with_selector(
+                [0x08, 0xc3, 0x79, 0xa0],
+                &[Token::String("paused".to_string())]
+            ))
+        ))),
+    };
+    assert_eq!(
+        SimulationError::from_rpc(Some(&reverted), "fallback"),
+        SimulationError::Reverted("paused".to_string())
+    );
+
+    let insufficient = JsonRpcError {
+        code: -32000,
+        message: "insufficient funds for gas * price + value".to_string(),
+        data: None,
+    };
+    assert_eq!(
+        SimulationError::from_rpc(Some(&insufficient), "fallback"),
+        SimulationError::Reverted("insufficient funds for gas * price + value".to_string())
+    );
+
+    let unsupported = JsonRpcError {
+        code: -32601,
+        message: "method not found".to_string(),
+        data: None,
+    };
+    assert!(matches!(
+        SimulationError::from_rpc(Some(&unsupported), "fallback"),
+        SimulationError::Unavailable(_)
+    ));
+    let reverted_without_data = JsonRpcError {
+        code: 3,
+        message: "execution failed".to_string(),
+        data: None,
+    };
+    assert!(matches!(
+        SimulationError::from_rpc(Some(&reverted_without_data), "fallback"),
+        SimulationError::Reverted(_)
+    ));
+
+    for (code, message) in [
+        (-32603, "internal error"),
+        (-32005, "rate limit exceeded"),
+        (-32000, "pending block is not available"),
+    ] {
+        let flaky = JsonRpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        };
+        assert_eq!(
+            SimulationError::from_rpc(Some(&flaky), "fallback"),
+            SimulationError::Unavailable(message.to_string()),
+            "{}",
+            code
+        );
+    }
+    assert_eq!(
+        SimulationError::from_rpc(None, "connection refused"),
+        SimulationError::Unavailable("connection refused".to_string())
+    );
+}
@@ -0,0 +1,52 @@
+use kite_ai_bot::service::api::sse_parser::{SseEvent, SseParser};
+
+fn data(value: &str) -> SseEvent {
+    SseEvent::Data(value.to_string())
+}
+
+#[test]
+fn event_split_across_chunks_is_buffered() {
+    let mut parser = SseParser::new();
+
+    assert!(parser.push(b"data: {\"choices\":[{\"de").is_empty());
+    assert_eq!(
+        parser.push(b"lta\":{\"content\":\"Hi\"}}]}\n\n"),
+        vec![data("{\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}")]
+    );
+    assert!(parser.finish().is_empty());
+}
+
+#[test]
+fn multiple_events_in_one_chunk_are_parsed_in_order() {
+    let mut parser = SseParser::new();
+
+    assert_eq!(
+        parser
+            .push(b": keep-alive\n\nevent: message\ndata: one\n\ndata:two\n\ndata: \n\ndata: thr"),
+        vec![data("one"), data("two")]
+    );
+    assert_eq!(parser.finish(), vec![data("thr")]);
+}
+
+#[test]
+fn crlf_line_endings_are_supported() {
+    let mut parser = SseParser::new();
+
+    assert_eq!(
+        parser.push(b"data: one\r\n\r\ndata: two\r"),
+        vec![data("one")]
+    );
+    assert_eq!(parser.push(b"\n\r\n"), vec![data("two")]);
+}
+
+#[test]
+fn done_marker_ends_the_stream() {
+    let mut parser = SseParser::new();
+
+    assert_eq!(
+        parser.push(b"data: one\n\ndata: [DONE]\n\ndata: late\n\n"),
+        vec![data("one"), SseEvent::Done]
+    );
+    assert!(parser.push(b"data: later\n\n").is_empty());
+    assert!(parser.finish().is_empty());
+}
@@ -0,0 +1,90 @@
+use ethers::{
+    abi::{self, Token},
+    providers::Provider,
+    types::{H160, U256},
+    utils::hex,
+};
+use kite_ai_bot::{
+    model::token_balance::TokenBalance,
+    service::evm_service::{
+        erc20::Erc20,
+        rpc_pool::{RpcHealthPolicy, RpcPool},
+    },
+};
+use rust_decimal::Decimal;
+use std::{str::FromStr, sync::Arc};
+
+mod common;
+
+use common::{network, serve};
+
+const TOKEN: &str = "0x0000000000000000000000000000000000000abc";
+
+fn decimal(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn balances_are_scaled_by_token_decimals() {
+    let usdt = TokenBalance::new(TOKEN, "USDT".to_string(), 6, U256::from(1_234_567u64)).unwrap();
+    assert_eq!(usdt.balance, decimal("1.234567"));
+
+    let weth = TokenBalance::new(TOKEN, "WETH".to_string(), 18, U256::exp10(18) * 5 / 2).unwrap();
+    assert_eq!(weth.balance, decimal("2.5"));
+
+    let raw = TokenBalance::new(TOKEN, "RAW".to_string(), 0, U256::from(42)).unwrap();
+    assert_eq!(raw.balance, decimal("42"));
+}
+
+#[test]
+fn unrepresentable_balance_is_an_error() {
+    let err = TokenBalance::new(TOKEN, "HUGE".to_string(), 0, U256::MAX).unwrap_err();
+    assert!(err.contains("HUGE"), "{}", err);
+}
+
+#[test]
+fn transfer_amounts_are_converted_to_token_units() {
+    let usdt = TokenBalance::new(TOKEN, "USDT".to_string(), 6, U256::zero()).unwrap();
+    assert_eq!(
+        usdt.raw_amount(decimal("1.5")).unwrap(),
+        U256::from(1_500_000u64)
+    );
+    assert!(usdt.raw_amount(decimal("0.0000001")).is_err());
+
+    let weth = TokenBalance::new(TOKEN, "WETH".to_string(), 18, U256::zero()).unwrap();
+    assert_eq!(weth.raw_amount(decimal("0.01")).unwrap(), U256::exp10(16));
+}
+
+fn eth_call_result(request: &str) -> String {
+    let data = if request.contains("0x95d89b41") {
+        abi::encode(&[Token::String("USDT".to_string())])
+    } else if request.contains("0x313ce567") {
+        abi::encode(&[Token::Uint(U256::from(6))])
+    } else {
+        abi::encode(&[Token::Uint(U256::from(2_500_000u64))])
+    };
+    format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":"0x{}"}}"#,
+        hex::encode(data)
+    )
+}
+
+#[tokio::test]
+async fn erc20_metadata_and_balance_are_decoded() {
+    let url = serve(|request| Some(eth_call_result(request))).await;
+
+    let pool = RpcPool::new(&network(vec![url]), None, RpcHealthPolicy::default()).unwrap();
+    let contract = Erc20::new(
+        TOKEN.parse::<H160>().unwrap(),
+        Arc::new(Provider::new(pool)),
+    );
+
+    let symbol = contract.symbol().call().await.unwrap();
+    let decimals = contract.decimals().call().await.unwrap();
+    let balance = contract.balance_of(H160::zero()).call().await.unwrap();
+    let token = TokenBalance::new(TOKEN, symbol, decimals, balance).unwrap();
+
+    assert_eq!(token.symbol, "USDT");
+    assert_eq!(token.decimals, 6);
+    assert_eq!(token.balance, decimal("2.5"));
+}